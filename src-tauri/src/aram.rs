@@ -0,0 +1,205 @@
+//! ARAM bench advisor. Ranks the local player's champion and the bench with
+//! u.gg ARAM win rates, and optionally swaps to a clearly better champion.
+
+use std::{collections::HashSet, iter};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime, async_runtime};
+use tauri_plugin_lcu::{
+    LcuExt,
+    models::champ_select::{BenchChampion, ChampSelectSession},
+};
+use tauri_plugin_store::{JsonValue, StoreExt};
+use tokio::time::{self, Duration};
+use ugg_types::{mappings, overview::Overview};
+
+use crate::{STORE_FILE, State, error::AramError, rank};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Store key of the auto swap setting.
+const AUTO_SWAP_KEY: &str = "aram_auto_swap";
+/// Minimum win rate gain over the current champion before swapping.
+const MIN_WIN_RATE_GAIN: f64 = 0.015;
+/// Minimum number of matches before a win rate is trusted.
+const MIN_MATCHES: i64 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchEntry {
+    pub champion_id: i64,
+    pub name: String,
+    pub win_rate: Option<f64>,
+    pub matches: i64,
+    pub owned: bool,
+    /// Whether this is the local player's current champion.
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchRanking {
+    /// Current and bench champions, sorted by win rate descending.
+    pub entries: Vec<BenchEntry>,
+    /// Bench champion with a clearly better win rate than the current one.
+    pub suggested_swap: Option<i64>,
+}
+
+/// Rank the current champ select bench.
+pub async fn rank_bench<R: Runtime>(app: &AppHandle<R>) -> crate::Result<BenchRanking> {
+    let session = app.lcu().get_champ_select_session().await?;
+    rank_session(app, &session).await
+}
+
+async fn rank_session<R: Runtime>(
+    app: &AppHandle<R>,
+    session: &ChampSelectSession,
+) -> crate::Result<BenchRanking> {
+    if !session.bench_enabled {
+        return Err(AramError::NoBench.into());
+    }
+    let current = session
+        .local_player()
+        .ok_or(AramError::NoLocalPlayer)?
+        .champion_id;
+    let owned = app
+        .lcu()
+        .get_owned_champions()
+        .await?
+        .into_iter()
+        .filter_map(|champ| champ.ownership.owned.then_some(champ.id))
+        .collect::<HashSet<_>>();
+
+    let state = app.state::<State>();
+    let client = state.client().await;
+    let region = *state.region.read().await;
+    let ranks = rank::preferred_ranks(app, mappings::Mode::ARAM).await;
+    let candidates = iter::once((current, true)).chain(
+        session
+            .bench_champions
+            .iter()
+            .map(|bench| (bench.champion_id, false)),
+    );
+    let mut entries = Vec::with_capacity(session.bench_champions.len() + 1);
+
+    for (champion_id, current) in candidates {
+        let Some(champ) = client.champion_by_key(champion_id) else {
+            continue;
+        };
+        let (win_rate, matches) = client
+            .get_overview(
+                champ,
+                mappings::Role::None,
+                region,
+                mappings::Mode::ARAM,
+                mappings::Build::Recommended,
+                &ranks,
+            )
+            .await
+            .map_or((None, 0), |(overview, ..)| win_rate(&overview));
+
+        entries.push(BenchEntry {
+            champion_id,
+            name: champ.name.clone(),
+            win_rate,
+            matches,
+            owned: owned.contains(&champion_id),
+            current,
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        let a = a.win_rate.unwrap_or(f64::NEG_INFINITY);
+        b.win_rate.unwrap_or(f64::NEG_INFINITY).total_cmp(&a)
+    });
+    let suggested_swap = suggest_swap(&entries);
+
+    Ok(BenchRanking {
+        entries,
+        suggested_swap,
+    })
+}
+
+/// Returns the win rate and number of matches of an overview.
+fn win_rate(overview: &Overview) -> (Option<f64>, i64) {
+    let (wins, matches) = match overview {
+        Overview::Default(data) => (data.wins, data.matches),
+        Overview::Arena(data) => (data.wins, data.matches),
+    };
+
+    ((matches > 0).then(|| wins as f64 / matches as f64), matches)
+}
+
+/// The best owned bench champion, if both its and the current champion's win
+/// rates are trusted and it beats the current one by at least
+/// [`MIN_WIN_RATE_GAIN`]. `entries` must be sorted by win rate descending.
+fn suggest_swap(entries: &[BenchEntry]) -> Option<i64> {
+    let trusted = |entry: &&BenchEntry| entry.matches >= MIN_MATCHES;
+    let best = entries
+        .iter()
+        .filter(trusted)
+        .find(|entry| !entry.current && entry.owned)?;
+    let current = entries.iter().find(|entry| entry.current).filter(trusted)?;
+
+    (best.win_rate? - current.win_rate? >= MIN_WIN_RATE_GAIN).then_some(best.champion_id)
+}
+
+fn auto_swap_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.store(STORE_FILE)
+        .is_ok_and(|store| matches!(store.get(AUTO_SWAP_KEY), Some(JsonValue::Bool(true))))
+}
+
+/// Rank the bench whenever it changes during ARAM champ select. Emits
+/// `aram-bench` with the ranking, and swaps champions if auto swap is enabled.
+pub fn watch<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<State>();
+    let cancel_token = state.cancel_token.clone();
+    let app = app.clone();
+
+    async_runtime::spawn(state.tracker.track_future(async move {
+        let mut interval = time::interval(POLL_INTERVAL);
+        let mut last_seen = None;
+
+        loop {
+            tokio::select! {
+                biased;
+                () = cancel_token.cancelled() => break,
+                _ = interval.tick() => poll(&app, &mut last_seen).await,
+            }
+        }
+    }));
+}
+
+async fn poll<R: Runtime>(app: &AppHandle<R>, last_seen: &mut Option<(i64, Vec<BenchChampion>)>) {
+    if !app.lcu().connected().await {
+        return;
+    }
+    let session = match app.lcu().get_champ_select_session().await {
+        Ok(session) if session.bench_enabled => session,
+        _ => {
+            *last_seen = None;
+            return;
+        }
+    };
+
+    let seen = (
+        session
+            .local_player()
+            .map_or(0, |player| player.champion_id),
+        session.bench_champions.clone(),
+    );
+    if last_seen.as_ref() == Some(&seen) {
+        return;
+    }
+    *last_seen = Some(seen);
+
+    let Ok(ranking) = rank_session(app, &session).await else {
+        return;
+    };
+    _ = app.emit("aram-bench", &ranking);
+
+    if let Some(champion_id) = ranking.suggested_swap
+        && auto_swap_enabled(app)
+    {
+        _ = app.lcu().bench_swap(champion_id).await;
+    }
+}
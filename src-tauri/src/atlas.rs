@@ -0,0 +1,168 @@
+//! Sprite atlases of all champion or item icons, so lists need one image
+//! instead of one per icon. Champions use the full-size icons, items the
+//! small sprite sheet crops.
+//!
+//! The atlas image is served at `ddragon://localhost/atlas/{kind}`, see
+//! [`protocol`](crate::protocol), and [`index`] has the icon coordinates.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use image::{DynamicImage, RgbaImage, imageops};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Manager, Runtime,
+    async_runtime::{self, Mutex},
+};
+
+use crate::State;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AtlasKind {
+    Champion,
+    Item,
+}
+
+impl AtlasKind {
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "champion" => Some(Self::Champion),
+            "item" => Some(Self::Item),
+            _ => None,
+        }
+    }
+}
+
+/// Pixel rect of an icon in the atlas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasIndex {
+    /// ddragon version of the icons.
+    pub version: String,
+    pub width: u32,
+    pub height: u32,
+    /// Champion ID like `Ahri`, or item ID like `3089`, to its rect.
+    pub icons: BTreeMap<String, Rect>,
+}
+
+#[derive(Debug)]
+pub struct Atlas {
+    pub image: DynamicImage,
+    pub index: AtlasIndex,
+}
+
+/// Atlases of the current version.
+#[derive(Debug, Default)]
+pub struct AtlasCache(Mutex<HashMap<(String, AtlasKind), Arc<Atlas>>>);
+
+/// Lay out `icons` in a square grid of cells the size of the largest icon.
+fn pack(version: String, icons: Vec<(String, DynamicImage)>) -> Atlas {
+    let cell_w = icons
+        .iter()
+        .map(|(_, icon)| icon.width())
+        .max()
+        .unwrap_or(0);
+    let cell_h = icons
+        .iter()
+        .map(|(_, icon)| icon.height())
+        .max()
+        .unwrap_or(0);
+    let columns = (icons.len() as f64).sqrt().ceil().max(1.0) as u32;
+    let rows = (icons.len() as u32).div_ceil(columns);
+
+    let mut image = RgbaImage::new(columns * cell_w, rows * cell_h);
+    let mut index = BTreeMap::new();
+    for (i, (id, icon)) in (0..).zip(icons) {
+        let (x, y) = ((i % columns) * cell_w, (i / columns) * cell_h);
+        imageops::replace(&mut image, &icon.into_rgba8(), x.into(), y.into());
+        let rect = Rect {
+            x,
+            y,
+            w: cell_w,
+            h: cell_h,
+        };
+        index.insert(id, rect);
+    }
+
+    Atlas {
+        index: AtlasIndex {
+            version,
+            width: image.width(),
+            height: image.height(),
+            icons: index,
+        },
+        image: DynamicImage::ImageRgba8(image),
+    }
+}
+
+/// The `kind` atlas of the current version, built once per version.
+pub async fn atlas<R: Runtime>(app: &AppHandle<R>, kind: AtlasKind) -> crate::Result<Arc<Atlas>> {
+    let client = app.state::<State>().client().await;
+    let version = client.version().to_owned();
+    let cache = app.state::<AtlasCache>();
+    // Held while building so an atlas isn't built twice at once.
+    let mut atlases = cache.0.lock().await;
+    if let Some(atlas) = atlases.get(&(version.clone(), kind)) {
+        return Ok(Arc::clone(atlas));
+    }
+
+    let ddragon = client.ddragon();
+    let icons = match kind {
+        // Full-size icons, since champion lists show them large.
+        AtlasKind::Champion => {
+            let tasks = client
+                .champions()
+                .iter()
+                .collect::<BTreeMap<_, _>>()
+                .into_iter()
+                .map(|(id, champ)| {
+                    let (id, champ, ddragon) = (id.clone(), champ.clone(), ddragon.clone());
+                    async_runtime::spawn(async move {
+                        Ok::<_, crate::Error>((id, ddragon.get_image_of(&champ).await?))
+                    })
+                })
+                .collect::<Vec<_>>();
+            let mut icons = Vec::with_capacity(tasks.len());
+            for task in tasks {
+                icons.push(task.await??);
+            }
+            icons
+        }
+        // Small icons cropped from the sprite sheets.
+        AtlasKind::Item => {
+            let images = client
+                .items()
+                .iter()
+                .map(|(id, item)| (id.clone(), &item.image))
+                .collect::<BTreeMap<_, _>>();
+            let mut icons = Vec::with_capacity(images.len());
+            for (id, image) in images {
+                icons.push((id, ddragon.get_sprite_of(image).await?));
+            }
+            icons
+        }
+    };
+
+    let atlas = Arc::new(async_runtime::spawn_blocking(move || pack(version, icons)).await?);
+    atlases.retain(|(version, _), _| *version == atlas.index.version);
+    atlases.insert((atlas.index.version.clone(), kind), Arc::clone(&atlas));
+    drop(atlases);
+    Ok(atlas)
+}
+
+/// Icon coordinates in the `kind` atlas.
+pub async fn index<R: Runtime>(app: &AppHandle<R>, kind: AtlasKind) -> crate::Result<AtlasIndex> {
+    Ok(atlas(app, kind).await?.index.clone())
+}
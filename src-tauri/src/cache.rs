@@ -0,0 +1,373 @@
+//! HTTP cache of ddragon, CommunityDragon, and u.gg responses.
+//!
+//! Versioned ddragon and CommunityDragon paths never change, so they're cached
+//! forever. Lists that change with new patches are revalidated, and everything
+//! else follows the response's cache headers. Data of patches older than the
+//! current one is evicted, and the least recently written entries are evicted
+//! over [`SIZE_CAP`].
+//!
+//! The cache used to be right in the app cache dir and was never pruned, so
+//! it's removed from there. The bundled dragontail is unpacked next to the
+//! cache in [`DRAGONTAIL_DIR`], and its size is reported separately.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use cacache::Metadata;
+use http_cache_reqwest::{CacheMode, Parts};
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime, async_runtime};
+use url::Url;
+
+/// Dir of the HTTP cache in the app cache dir.
+const CACHE_DIR: &str = "http";
+/// Dir of the unpacked bundled dragontail in the app cache dir.
+const DRAGONTAIL_DIR: &str = "dragontail";
+/// cacache dirs of the cache from before it moved to [`CACHE_DIR`].
+const LEGACY_DIRS: [&str; 3] = ["index-v5", "content-v2", "tmp"];
+/// Max total size of cached responses in bytes.
+pub const SIZE_CAP: u64 = 512 * 1024 * 1024;
+/// Paths of lists that change with new patches.
+const LISTS: [&str; 3] = ["/api/versions.json", "/cdn/languages.json", "/json/"];
+
+/// A patch as major and minor version, e.g. `15.7`.
+type Patch = (u32, u32);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheUsage {
+    /// Total size in bytes.
+    pub size: u64,
+    /// See [`SIZE_CAP`].
+    pub cap: u64,
+    /// Newest patch first, then responses of no patch.
+    pub patches: Vec<PatchUsage>,
+    /// Size of the unpacked dragontail in bytes, not part of `size`.
+    pub dragontail: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchUsage {
+    /// Like `15.7`, or `None` for responses of no patch.
+    pub patch: Option<String>,
+    /// Size in bytes.
+    pub size: u64,
+    pub entries: usize,
+}
+
+pub fn dir<R: Runtime>(app: &AppHandle<R>) -> crate::Result<PathBuf> {
+    Ok(app.path().app_cache_dir()?.join(CACHE_DIR))
+}
+
+/// Dir to unpack the bundled dragontail into.
+pub fn dragontail_dir<R: Runtime>(app: &AppHandle<R>) -> crate::Result<PathBuf> {
+    Ok(app.path().app_cache_dir()?.join(DRAGONTAIL_DIR))
+}
+
+/// Cache mode of a request, see the [module docs](self).
+pub fn mode(parts: &Parts) -> CacheMode {
+    let path = parts.uri.path();
+    if LISTS.contains(&path) || path.starts_with("/realms/") {
+        return CacheMode::NoCache;
+    }
+
+    let mut segments = path.trim_start_matches('/').split('/');
+    match (segments.next(), segments.next()) {
+        (Some("cdn"), Some(version))
+            if parse_version(version).is_some() || version.starts_with("dragontail-") =>
+        {
+            CacheMode::ForceCache
+        }
+        // CommunityDragon, like `/15.7/game/...`.
+        (Some(version), _) if parse_version(version).is_some() => CacheMode::ForceCache,
+        _ => CacheMode::Default,
+    }
+}
+
+/// Parse a ddragon version like `15.7.1`, or a u.gg patch like `15_7`.
+pub fn parse_version(version: &str) -> Option<Patch> {
+    let mut parts = version.split(['.', '_']).map(str::parse::<u32>);
+    match (parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor))) if parts.all(|part| part.is_ok()) => {
+            Some((major, minor))
+        }
+        _ => None,
+    }
+}
+
+/// Patch of a cache key like `GET:https://ddragon.leagueoflegends.com/cdn/15.7.1/...`.
+///
+/// The patch is at a different segment per host: `/cdn/{version}/` on
+/// ddragon, `/{version}/` on CommunityDragon, and
+/// `/lol/{api version}/{build}/{patch}/` on u.gg.
+fn key_patch(key: &str) -> Option<Patch> {
+    let (_, url) = key.split_once(':')?;
+    let url = Url::parse(url).ok()?;
+    let mut segments = url.path_segments()?;
+    let version = match url.host_str()? {
+        "raw.communitydragon.org" => segments.next(),
+        host if host == "u.gg" || host.ends_with(".u.gg") => segments.nth(3),
+        _ => match segments.next() {
+            Some("cdn") => segments.next(),
+            _ => None,
+        },
+    }?;
+    parse_version(version)
+}
+
+fn entries(dir: &Path) -> crate::Result<Vec<Metadata>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(cacache::list_sync(dir).collect::<Result<_, _>>()?)
+}
+
+/// Remove `evicted` entries, and their content if no other entry has it.
+fn remove(
+    dir: &Path,
+    entries: Vec<Metadata>,
+    mut evicted: impl FnMut(&Metadata) -> bool,
+) -> crate::Result<()> {
+    let (evicted, kept) = entries
+        .into_iter()
+        .partition::<Vec<_>, _>(|entry| evicted(entry));
+    let kept = kept
+        .iter()
+        .map(|entry| &entry.integrity)
+        .collect::<HashSet<_>>();
+
+    for entry in &evicted {
+        cacache::remove_sync(dir, &entry.key)?;
+        if !kept.contains(&entry.integrity) {
+            cacache::remove_hash_sync(dir, &entry.integrity)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Total size of the files in `dir`, or 0 if it doesn't exist.
+fn dir_size(dir: &Path) -> io::Result<u64> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let mut size = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+
+    Ok(size)
+}
+
+fn usage_sync(dir: &Path, dragontail: &Path) -> crate::Result<CacheUsage> {
+    let mut patches = BTreeMap::<Reverse<Option<Patch>>, (u64, usize)>::new();
+    for entry in entries(dir)? {
+        let usage = patches.entry(Reverse(key_patch(&entry.key))).or_default();
+        usage.0 += entry.size as u64;
+        usage.1 += 1;
+    }
+
+    Ok(CacheUsage {
+        size: patches.values().map(|(size, _)| size).sum(),
+        cap: SIZE_CAP,
+        patches: patches
+            .into_iter()
+            .map(|(Reverse(patch), (size, entries))| PatchUsage {
+                patch: patch.map(|(major, minor)| format!("{major}.{minor}")),
+                size,
+                entries,
+            })
+            .collect(),
+        dragontail: dir_size(dragontail)?,
+    })
+}
+
+/// Evict data of patches older than `version`, then the least recently
+/// written entries over [`SIZE_CAP`].
+fn prune_sync(dir: &Path, version: &str) -> crate::Result<()> {
+    let current = parse_version(version);
+    let mut entries = entries(dir)?;
+    entries.sort_unstable_by_key(|entry| Reverse(entry.time));
+
+    let mut size = 0;
+    remove(dir, entries, |entry| {
+        if key_patch(&entry.key).is_some_and(|patch| current.is_some_and(|current| patch < current))
+        {
+            return true;
+        }
+        size += entry.size as u64;
+        size > SIZE_CAP
+    })
+}
+
+/// Remove the cache from before it moved to [`CACHE_DIR`] in `app_cache`.
+fn remove_legacy(app_cache: &Path) -> io::Result<()> {
+    let legacy = LEGACY_DIRS.iter().map(|dir| app_cache.join(dir));
+    // The dragontail used to be unpacked in the cache.
+    let dragontail = app_cache.join(CACHE_DIR).join(DRAGONTAIL_DIR);
+    for dir in legacy.chain([dragontail]) {
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Size of the cache per patch.
+pub async fn usage<R: Runtime>(app: &AppHandle<R>) -> crate::Result<CacheUsage> {
+    let (dir, dragontail) = (dir(app)?, dragontail_dir(app)?);
+    async_runtime::spawn_blocking(move || usage_sync(&dir, &dragontail)).await?
+}
+
+/// Clear the cached responses of `patch` (like `15.7`), or all of them if
+/// `None`. Returns the remaining usage.
+pub async fn clear<R: Runtime>(
+    app: &AppHandle<R>,
+    patch: Option<String>,
+) -> crate::Result<CacheUsage> {
+    let (dir, dragontail) = (dir(app)?, dragontail_dir(app)?);
+    async_runtime::spawn_blocking(move || {
+        remove(&dir, entries(&dir)?, |entry| {
+            patch.as_ref().is_none_or(|patch| {
+                key_patch(&entry.key)
+                    .is_some_and(|(major, minor)| *patch == format!("{major}.{minor}"))
+            })
+        })?;
+        usage_sync(&dir, &dragontail)
+    })
+    .await?
+}
+
+/// Evict old and excess entries in the background after the patch changed to
+/// `version`, see [`prune_sync`]. Also removes the legacy cache.
+pub fn prune<R: Runtime>(app: &AppHandle<R>, version: &str) {
+    let Ok(app_cache) = app.path().app_cache_dir() else {
+        return;
+    };
+    let version = version.to_owned();
+    async_runtime::spawn_blocking(move || {
+        _ = remove_legacy(&app_cache);
+        _ = prune_sync(&app_cache.join(CACHE_DIR), &version);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::http::Request;
+
+    use super::*;
+
+    fn parts(url: &str) -> Parts {
+        Request::get(url).body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(parse_version("15.7.1"), Some((15, 7)));
+        assert_eq!(parse_version("15.7"), Some((15, 7)));
+        assert_eq!(parse_version("15_7"), Some((15, 7)));
+        assert_eq!(parse_version("lolpatch_7.17"), None);
+        assert_eq!(parse_version("latest"), None);
+        assert_eq!(parse_version("15"), None);
+    }
+
+    #[test]
+    fn reads_key_patches() {
+        let patch = |url: &str| key_patch(&format!("GET:{url}"));
+
+        assert_eq!(
+            patch("https://ddragon.leagueoflegends.com/cdn/15.7.1/data/en_US/champion.json"),
+            Some((15, 7))
+        );
+        assert_eq!(
+            patch("https://ddragon.leagueoflegends.com/cdn/15.7.1/img/sprite/champion0.png"),
+            Some((15, 7))
+        );
+        assert_eq!(
+            patch("https://ddragon.leagueoflegends.com/cdn/img/champion/splash/Ahri_0.jpg"),
+            None
+        );
+        assert_eq!(
+            patch("https://ddragon.leagueoflegends.com/api/versions.json"),
+            None
+        );
+        assert_eq!(
+            patch("https://raw.communitydragon.org/15.7/game/data/characters/ahri/ahri.bin.json"),
+            Some((15, 7))
+        );
+        assert_eq!(
+            patch("https://raw.communitydragon.org/latest/cdragon/arena/en_us.json"),
+            None
+        );
+        assert_eq!(
+            patch("https://stats2.u.gg/lol/1.5/overview/15_7/ranked_solo_5x5/103/1.5.0.json"),
+            Some((15, 7))
+        );
+        assert_eq!(
+            patch("https://stats2.u.gg/lol/1.5/matchups/15_6/ranked_solo_5x5/103/1.5.0.json"),
+            Some((15, 6))
+        );
+        assert_eq!(
+            patch(
+                "https://static.bigbrain.gg/assets/lol/riot_patch_update/prod/ugg/ugg-api-versions.json"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn picks_cache_modes() {
+        let mode = |url: &str| mode(&parts(url));
+
+        assert_eq!(
+            mode("https://ddragon.leagueoflegends.com/api/versions.json"),
+            CacheMode::NoCache
+        );
+        assert_eq!(
+            mode("https://ddragon.leagueoflegends.com/cdn/languages.json"),
+            CacheMode::NoCache
+        );
+        assert_eq!(
+            mode("https://ddragon.leagueoflegends.com/realms/na.json"),
+            CacheMode::NoCache
+        );
+        assert_eq!(
+            mode("https://ddragon.leagueoflegends.com/cdn/15.7.1/data/en_US/champion.json"),
+            CacheMode::ForceCache
+        );
+        assert_eq!(
+            mode("https://ddragon.leagueoflegends.com/cdn/dragontail-15.7.1.tgz"),
+            CacheMode::ForceCache
+        );
+        assert_eq!(
+            mode("https://raw.communitydragon.org/json/"),
+            CacheMode::NoCache
+        );
+        assert_eq!(
+            mode(
+                "https://raw.communitydragon.org/15.7/plugins/rcp-be-lol-game-data/global/default/v1/perks.json"
+            ),
+            CacheMode::ForceCache
+        );
+        assert_eq!(
+            mode("https://raw.communitydragon.org/latest/cdragon/arena/en_us.json"),
+            CacheMode::Default
+        );
+        assert_eq!(
+            mode("https://stats2.u.gg/lol/1.5/overview/15_7/ranked_solo_5x5/103/1.5.0.json"),
+            CacheMode::Default
+        );
+    }
+}
@@ -0,0 +1,402 @@
+//! CommunityDragon client for data that data dragon doesn't have: detailed
+//! ability data from champion bins, client perk data, skins with chromas, and
+//! arena augments.
+//!
+//! Data is pinned to the ddragon patch, e.g. `15.7` for `15.7.1`, or `latest`
+//! if CommunityDragon doesn't have the patch yet.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use image::DynamicImage;
+use reqwest_middleware::ClientWithMiddleware;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use tauri_plugin_http::reqwest::{Client as ReqwestClient, Url};
+
+use crate::{
+    cache,
+    ddragon::{self, ClientType},
+};
+
+const BASE_URL: &str = "https://raw.communitydragon.org";
+const LATEST: &str = "latest";
+/// Prefix of asset paths in game data, like
+/// `/lol-game-data/assets/v1/perk-images/...`.
+const GAME_DATA_ASSETS: &str = "/lol-game-data/assets/";
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    client: ClientWithMiddleware,
+    /// Patch like `15.7`, or `latest`.
+    version: String,
+    base_url: Url,
+    /// ddragon locale like `en_US`.
+    locale: String,
+}
+
+#[derive(Debug)]
+pub struct ClientBuilder<'a> {
+    cache_path: Option<PathBuf>,
+    version: Option<&'a str>,
+    locale: Option<&'a str>,
+}
+
+/// An entry of a directory listing from `/json/`.
+#[derive(Debug, Deserialize)]
+struct ListingEntry {
+    name: String,
+}
+
+impl<'a> ClientBuilder<'a> {
+    pub const fn new() -> Self {
+        Self {
+            cache_path: None,
+            version: None,
+            locale: None,
+        }
+    }
+
+    pub fn cache<P: Into<PathBuf>>(mut self, cache_path: P) -> Self {
+        self.cache_path = Some(cache_path.into());
+        self
+    }
+
+    /// Pin data to the patch of a ddragon version like `15.7.1`.
+    pub const fn version(mut self, version: &'a str) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// ddragon locale like `en_US`.
+    pub const fn locale(mut self, locale: &'a str) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Use the version and locale of a ddragon client.
+    pub const fn ddragon(self, client: &'a ddragon::Client) -> Self {
+        self.version(client.version()).locale(client.locale())
+    }
+
+    /// Builds the [`Client`], with caching middleware if `cache_path` is set.
+    pub async fn build(self) -> crate::Result<Client> {
+        let client = ClientType::Plain(ReqwestClient::builder().brotli(true).build()?);
+        let client = ddragon::ClientBuilder::middleware_client(client, self.cache_path);
+        let base_url = Url::parse(BASE_URL)?;
+
+        let version = match self
+            .version
+            .and_then(cache::parse_version)
+            .map(|(major, minor)| format!("{major}.{minor}"))
+        {
+            // Requests fail later anyway if the listing can't be fetched,
+            // e.g. offline with a bundled dragontail.
+            Some(patch) => match Self::patches(&client, &base_url).await {
+                Ok(patches) if patches.iter().any(|entry| entry.name == patch) => patch,
+                _ => LATEST.to_owned(),
+            },
+            None => LATEST.to_owned(),
+        };
+
+        Ok(Client {
+            client,
+            version,
+            base_url,
+            locale: self.locale.unwrap_or("en_US").to_owned(),
+        })
+    }
+
+    async fn patches(
+        client: &ClientWithMiddleware,
+        base_url: &Url,
+    ) -> crate::Result<Vec<ListingEntry>> {
+        Ok(client
+            .get(base_url.join("/json/")?)
+            .send()
+            .await?
+            .json()
+            .await?)
+    }
+}
+
+/// A champion from game data, with abilities and skins.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Champion {
+    pub id: i64,
+    pub name: String,
+    /// Like ddragon's champion ID, e.g. `MonkeyKing`.
+    pub alias: String,
+    pub title: String,
+    pub short_bio: String,
+    pub roles: Vec<String>,
+    pub square_portrait_path: String,
+    pub passive: Passive,
+    pub spells: Vec<Spell>,
+    pub skins: Vec<Skin>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Passive {
+    pub name: String,
+    pub ability_icon_path: String,
+    pub description: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Spell {
+    /// `q`, `w`, `e`, or `r`.
+    pub spell_key: String,
+    pub name: String,
+    pub ability_icon_path: String,
+    pub description: String,
+    /// Description with `@Effect1Amount@`-style placeholders.
+    pub dynamic_description: String,
+    pub max_level: i64,
+    /// Per rank.
+    pub cooldown_coefficients: Vec<f64>,
+    /// Per rank.
+    pub cost_coefficients: Vec<f64>,
+    /// Per rank.
+    pub range: Vec<f64>,
+    /// Effect name like `Effect1Amount` to values per rank.
+    pub effect_amounts: HashMap<String, Vec<f64>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Skin {
+    pub id: i64,
+    pub is_base: bool,
+    pub name: String,
+    pub splash_path: String,
+    pub uncentered_splash_path: String,
+    pub tile_path: String,
+    pub load_screen_path: String,
+    /// Like `kEpic`, or `kNoRarity`.
+    pub rarity: String,
+    pub is_legacy: bool,
+    pub chromas: Vec<Chroma>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Chroma {
+    pub id: i64,
+    pub name: String,
+    pub chroma_path: String,
+    /// Hex colors like `#C94F4F`.
+    pub colors: Vec<String>,
+}
+
+/// A rune as shown in the client.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Perk {
+    pub id: i64,
+    pub name: String,
+    pub major_change_patch_version: String,
+    pub tooltip: String,
+    pub short_desc: String,
+    pub long_desc: String,
+    pub icon_path: String,
+    pub end_of_game_stat_descs: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+struct Arena {
+    augments: Vec<Augment>,
+}
+
+/// An arena augment.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Augment {
+    pub id: i64,
+    pub api_name: String,
+    pub name: String,
+    pub desc: String,
+    pub tooltip: String,
+    /// Path under `game/`, see [`Client::asset_url`].
+    pub icon_large: String,
+    pub icon_small: String,
+    /// 0 silver, 1 gold, 2 prismatic.
+    pub rarity: i64,
+    pub data_values: HashMap<String, f64>,
+}
+
+/// A champion with the data from its bin.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionDetails {
+    pub champion: Champion,
+    pub stats: Option<CharacterRecord>,
+    pub abilities: Vec<SpellObject>,
+}
+
+/// A champion's `.bin.json`: game objects by path, like
+/// `Characters/Ahri/CharacterRecords/Root`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ChampionBin(pub HashMap<String, Value>);
+
+/// Base stats of a champion, from its `CharacterRecord`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CharacterRecord {
+    #[serde(rename(deserialize = "mCharacterName"))]
+    pub character_name: String,
+    #[serde(rename(deserialize = "baseHP"))]
+    pub base_hp: f64,
+    #[serde(rename = "hpPerLevel")]
+    pub hp_per_level: f64,
+    #[serde(rename(deserialize = "baseStaticHPRegen"))]
+    pub base_static_hp_regen: f64,
+    pub base_armor: f64,
+    pub armor_per_level: f64,
+    pub base_spell_block: f64,
+    pub spell_block_per_level: f64,
+    pub base_move_speed: f64,
+    pub attack_range: f64,
+    pub attack_speed: f64,
+    pub attack_speed_ratio: f64,
+    pub attack_speed_per_level: f64,
+    pub base_damage: f64,
+    pub damage_per_level: f64,
+    /// Script names of the abilities, see [`SpellObject::script_name`].
+    pub spell_names: Vec<String>,
+}
+
+/// An ability's game object.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SpellObject {
+    #[serde(rename(deserialize = "mScriptName"))]
+    pub script_name: String,
+    #[serde(rename(deserialize = "mSpell"))]
+    pub spell: SpellData,
+}
+
+/// Values per rank are indexed from rank 0 to 6, though most abilities only
+/// use 1 to 5.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SpellData {
+    pub cooldown_time: Vec<f64>,
+    pub cast_range: Vec<f64>,
+    pub mana: Vec<f64>,
+    #[serde(rename(deserialize = "mDataValues"))]
+    pub data_values: Vec<DataValue>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DataValue {
+    #[serde(rename(deserialize = "mName"))]
+    pub name: String,
+    #[serde(rename(deserialize = "mValues"))]
+    pub values: Vec<f64>,
+}
+
+impl ChampionBin {
+    /// Game objects of the bin class `kind`, like `SpellObject`.
+    fn objects<'a, T: DeserializeOwned>(&'a self, kind: &'a str) -> impl Iterator<Item = T> + 'a {
+        self.0
+            .values()
+            .filter(move |object| object.get("__type").and_then(Value::as_str) == Some(kind))
+            .filter_map(|object| T::deserialize(object).ok())
+    }
+
+    pub fn character_record(&self) -> Option<CharacterRecord> {
+        self.objects("CharacterRecord").next()
+    }
+
+    pub fn spells(&self) -> Vec<SpellObject> {
+        self.objects("SpellObject").collect()
+    }
+}
+
+impl Client {
+    /// Locale of game data paths, like `default` for `en_US` or `ko_kr`.
+    fn game_data_locale(&self) -> String {
+        match self.locale.as_str() {
+            "en_US" => "default".to_owned(),
+            locale => locale.to_ascii_lowercase(),
+        }
+    }
+
+    /// URL of an asset path from game data, like
+    /// `/lol-game-data/assets/v1/perk-images/...`, or under `game/`, like
+    /// `assets/ux/cherry/augments/...`.
+    pub fn asset_url(&self, path: &str) -> crate::Result<Url> {
+        let path = path.to_ascii_lowercase();
+        let path = path.strip_prefix(GAME_DATA_ASSETS).map_or_else(
+            || format!("game/{}", path.trim_start_matches('/')),
+            |path| format!("plugins/rcp-be-lol-game-data/global/default/{path}"),
+        );
+        Ok(self.base_url.join(&format!("/{}/{path}", self.version))?)
+    }
+
+    /// Get JSON at `path` under the patch.
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> crate::Result<T> {
+        let url = self.base_url.join(&format!("/{}/{path}", self.version))?;
+        Ok(self.client.get(url).send().await?.json::<T>().await?)
+    }
+
+    /// Get a game data file by its path like `v1/perks.json`.
+    async fn get_game_data<T: DeserializeOwned>(&self, path: &str) -> crate::Result<T> {
+        self.get(&format!(
+            "plugins/rcp-be-lol-game-data/global/{}/{path}",
+            self.game_data_locale()
+        ))
+        .await
+    }
+
+    /// Get a champion by its numeric key, as used by the LCU API.
+    pub async fn get_champion(&self, key: i64) -> crate::Result<Champion> {
+        self.get_game_data(&format!("v1/champions/{key}.json"))
+            .await
+    }
+
+    /// Get a champion's bin by its alias, like `Ahri`.
+    pub async fn get_champion_bin(&self, alias: &str) -> crate::Result<ChampionBin> {
+        let alias = alias.to_ascii_lowercase();
+        self.get(&format!("game/data/characters/{alias}/{alias}.bin.json"))
+            .await
+    }
+
+    /// Get a champion's game data with the abilities and base stats from its
+    /// bin.
+    pub async fn get_champion_details(&self, key: i64) -> crate::Result<ChampionDetails> {
+        let champion = self.get_champion(key).await?;
+        let bin = self.get_champion_bin(&champion.alias).await?;
+
+        Ok(ChampionDetails {
+            stats: bin.character_record(),
+            abilities: bin.spells(),
+            champion,
+        })
+    }
+
+    pub async fn get_perks(&self) -> crate::Result<Vec<Perk>> {
+        self.get_game_data("v1/perks.json").await
+    }
+
+    pub async fn get_arena_augments(&self) -> crate::Result<Vec<Augment>> {
+        Ok(self
+            .get::<Arena>(&format!(
+                "cdragon/arena/{}.json",
+                self.locale.to_ascii_lowercase()
+            ))
+            .await?
+            .augments)
+    }
+
+    /// Get an image by its asset path, see [`Self::asset_url`].
+    pub async fn get_image(&self, path: &str) -> crate::Result<DynamicImage> {
+        let response = self.client.get(self.asset_url(path)?).send().await?;
+        Ok(image::load_from_memory(&response.bytes().await?)?)
+    }
+}
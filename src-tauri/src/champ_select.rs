@@ -0,0 +1,211 @@
+//! Auto hover, pick, and ban in champ select from the user's preferences for
+//! their assigned position.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, async_runtime};
+use tauri_plugin_lcu::{
+    LcuExt,
+    models::champ_select::{ChampSelectAction, ChampSelectSession},
+};
+use tauri_plugin_store::StoreExt;
+use tokio::time::{self, Duration};
+
+use crate::{STORE_FILE, State};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Store key of the auto pick preferences.
+const AUTO_PICK_KEY: &str = "auto_pick";
+/// Position whose preferences are used for blind pick and positions without
+/// preferences.
+const ANY_POSITION: &str = "any";
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Preferences {
+    pub enabled: bool,
+    /// Lock in picks and bans instead of only hovering them.
+    pub lock: bool,
+    /// Keyed by assigned position (`top`, `jungle`, `middle`, `bottom`,
+    /// `utility`) or [`ANY_POSITION`].
+    pub positions: HashMap<String, Champions>,
+}
+
+/// Champion IDs in order of preference.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Champions {
+    pub picks: Vec<i64>,
+    pub bans: Vec<i64>,
+}
+
+impl Preferences {
+    fn champions(&self, position: &str) -> Option<&Champions> {
+        self.positions
+            .get(&position.to_ascii_lowercase())
+            .or_else(|| self.positions.get(ANY_POSITION))
+    }
+}
+
+/// The user's auto pick preferences.
+pub fn preferences<R: Runtime>(app: &AppHandle<R>) -> Preferences {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get(AUTO_PICK_KEY))
+        .and_then(|preferences| serde_json::from_value(preferences).ok())
+        .unwrap_or_default()
+}
+
+/// Save the user's auto pick preferences. Positions are lowercased, and
+/// positions without champions are removed.
+pub fn set_preferences<R: Runtime>(
+    app: &AppHandle<R>,
+    mut preferences: Preferences,
+) -> crate::Result<Preferences> {
+    preferences.positions = preferences
+        .positions
+        .into_iter()
+        .filter(|(_, champions)| !champions.picks.is_empty() || !champions.bans.is_empty())
+        .map(|(position, champions)| (position.to_ascii_lowercase(), champions))
+        .collect();
+    app.store(STORE_FILE)?
+        .set(AUTO_PICK_KEY, serde_json::to_value(&preferences)?);
+
+    Ok(preferences)
+}
+
+/// Champions picked, hovered, or banned by anyone but the local player.
+fn taken_champions(session: &ChampSelectSession) -> HashSet<i64> {
+    let players = session
+        .my_team
+        .iter()
+        .chain(&session.their_team)
+        .filter(|player| player.cell_id != session.local_player_cell_id)
+        .flat_map(|player| [player.champion_id, player.champion_pick_intent]);
+    let bans = session
+        .actions
+        .iter()
+        .flatten()
+        .filter(|action| action.kind == "ban" && action.completed)
+        .map(|action| action.champion_id)
+        .chain(session.bans.my_team_bans.iter().copied())
+        .chain(session.bans.their_team_bans.iter().copied());
+
+    players.chain(bans).filter(|&id| id > 0).collect()
+}
+
+/// Hover or pick and ban champions for the local player's turns during champ
+/// select, if enabled.
+pub fn watch<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<State>();
+    let cancel_token = state.cancel_token.clone();
+    let app = app.clone();
+
+    async_runtime::spawn(state.tracker.track_future(async move {
+        let mut interval = time::interval(POLL_INTERVAL);
+        let mut handled = Handled::default();
+
+        loop {
+            tokio::select! {
+                biased;
+                () = cancel_token.cancelled() => break,
+                _ = interval.tick() => poll(&app, &mut handled).await,
+            }
+        }
+    }));
+}
+
+/// Actions already acted on in a champ select, so the user can still change
+/// what was hovered for them.
+#[derive(Debug, Default)]
+struct Handled {
+    game_id: i64,
+    /// Action IDs, and whether the action was in progress.
+    actions: HashSet<(i64, bool)>,
+}
+
+async fn poll<R: Runtime>(app: &AppHandle<R>, handled: &mut Handled) {
+    if !app.lcu().connected().await {
+        return;
+    }
+    let preferences = preferences(app);
+    if !preferences.enabled {
+        return;
+    }
+    let Ok(session) = app.lcu().get_champ_select_session().await else {
+        return;
+    };
+    if session.game_id != handled.game_id {
+        *handled = Handled {
+            game_id: session.game_id,
+            actions: HashSet::new(),
+        };
+    }
+    let Some(champions) = session
+        .local_player()
+        .and_then(|player| preferences.champions(&player.assigned_position))
+    else {
+        return;
+    };
+
+    // Pick intent can be declared before the pick turn, while planning.
+    let planning = session.timer.phase == "PLANNING";
+    for action in session.actions.iter().flatten() {
+        let turn = action.actor_cell_id == session.local_player_cell_id
+            && !action.completed
+            && (action.is_in_progress || (planning && action.kind == "pick"));
+        let key = (action.id, action.is_in_progress);
+        // Retried on the next poll if it failed.
+        if turn
+            && !handled.actions.contains(&key)
+            && act(app, &session, action, champions, preferences.lock)
+                .await
+                .is_ok()
+        {
+            handled.actions.insert(key);
+        }
+    }
+}
+
+/// Hover or lock in the first available preferred champion for `action`. If
+/// a champion is already hovered, it's only locked in.
+async fn act<R: Runtime>(
+    app: &AppHandle<R>,
+    session: &ChampSelectSession,
+    action: &ChampSelectAction,
+    champions: &Champions,
+    lock: bool,
+) -> crate::Result<()> {
+    let lock = lock && action.is_in_progress;
+    let (preferred, available) = match action.kind.as_str() {
+        "pick" => (
+            &champions.picks,
+            app.lcu().get_pickable_champion_ids().await?,
+        ),
+        "ban" => (
+            &champions.bans,
+            app.lcu().get_bannable_champion_ids().await?,
+        ),
+        _ => return Ok(()),
+    };
+    let taken = taken_champions(session);
+    let available = |id: &i64| available.contains(id) && !taken.contains(id);
+
+    let champion_id = if action.champion_id > 0 {
+        if !lock || !available(&action.champion_id) {
+            return Ok(());
+        }
+        action.champion_id
+    } else {
+        let Some(&champion_id) = preferred.iter().find(|id| available(id)) else {
+            return Ok(());
+        };
+        champion_id
+    };
+
+    app.lcu()
+        .select_champion(action.id, champion_id, lock)
+        .await?;
+    Ok(())
+}
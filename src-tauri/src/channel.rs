@@ -2,19 +2,26 @@ use std::collections::HashMap;
 
 use ddragon::models::{Champion, champions::ChampionShort};
 use serde::Serialize;
-// use ugg_types::{mappings, matchups::MatchupData, overview::Overview};
+use ugg_types::{mappings, matchups::MatchupData, overview::Overview};
+
+use crate::aram::BenchRanking;
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase", tag = "type", content = "data")]
 pub enum KruggMessage {
     Champions(HashMap<String, ChampionShort>),
     Champion(Box<Champion>),
-    // Overview {
-    //     overview: Box<Overview>,
-    //     role: mappings::Role,
-    // },
-    // Matchups {
-    //     matchups: Box<MatchupData>,
-    //     role: mappings::Role,
-    // },
+    Overview {
+        overview: Box<Overview>,
+        role: mappings::Role,
+        /// Rank bracket the stats are from.
+        rank: mappings::Rank,
+    },
+    Matchups {
+        matchups: Box<MatchupData>,
+        role: mappings::Role,
+        /// Rank bracket the stats are from.
+        rank: mappings::Rank,
+    },
+    AramBench(BenchRanking),
 }
@@ -1,6 +1,25 @@
 use tauri::{AppHandle, Manager, Window, ipc::Channel};
+use tauri_plugin_lcu::models::replay::{Playback, PlaybackUpdate, Render};
+use ugg_types::mappings;
 
-use crate::{State, channel::KruggMessage};
+use crate::{
+    State, aram,
+    atlas::{self, AtlasIndex, AtlasKind},
+    cache::{self, CacheUsage},
+    cdragon::{Augment, ChampionDetails, Perk},
+    champ_select::{self, Preferences},
+    channel::KruggMessage,
+    error::DdragonError,
+    friends::{self, FriendsList},
+    locale::{self, RegionLocale},
+    loot::{self, LootAction, LootAdvice},
+    lp::{self, LpEntry, LpStreaks},
+    player::{self, ProfileBundle},
+    profiles::{self, SettingDiff},
+    queue, rank,
+    replay::{self, Moment},
+    scout::{self, PlayerSummary},
+};
 
 #[tauri::command]
 pub fn show_main_window(window: Window) -> crate::Result<()> {
@@ -17,7 +36,8 @@ pub fn show_main_window(window: Window) -> crate::Result<()> {
 #[tauri::command]
 pub async fn get_champions(app: AppHandle, channel: Channel<KruggMessage>) -> crate::Result<()> {
     let state = app.state::<State>();
-    let ddragon = state.client.ddragon();
+    let client = state.client().await;
+    let ddragon = client.ddragon();
     let champs = ddragon.get_champions().await?;
     channel.send(KruggMessage::Champions(champs))?;
 
@@ -31,9 +51,324 @@ pub async fn get_champion(
     id: &str,
 ) -> crate::Result<()> {
     let state = app.state::<State>();
-    let ddragon = state.client.ddragon();
+    let client = state.client().await;
+    let ddragon = client.ddragon();
     let champ = ddragon.get_champion(id).await?;
     channel.send(KruggMessage::Champion(champ))?;
 
     Ok(())
 }
+
+/// Get the CommunityDragon abilities, skins and base stats of the champion
+/// `id`, like `Ahri`.
+#[tauri::command]
+pub async fn get_champion_details(app: AppHandle, id: &str) -> crate::Result<ChampionDetails> {
+    let state = app.state::<State>();
+    let client = state.client().await;
+    let key = client
+        .champion(id)
+        .and_then(|champ| champ.key.parse().ok())
+        .ok_or_else(|| DdragonError::NoChampionData(id.to_owned()))?;
+
+    client.cdragon().get_champion_details(key).await
+}
+
+/// Get the runes as shown in the client.
+#[tauri::command]
+pub async fn get_perks(app: AppHandle) -> crate::Result<Vec<Perk>> {
+    let state = app.state::<State>();
+    state.client().await.cdragon().get_perks().await
+}
+
+#[tauri::command]
+pub async fn get_arena_augments(app: AppHandle) -> crate::Result<Vec<Augment>> {
+    let state = app.state::<State>();
+    state.client().await.cdragon().get_arena_augments().await
+}
+
+/// Get the u.gg overview of the champion `name` in the current region. The
+/// mode defaults to the current queue's. Sends the role and rank bracket the
+/// stats are from.
+#[tauri::command]
+pub async fn get_overview(
+    app: AppHandle,
+    channel: Channel<KruggMessage>,
+    name: &str,
+    role: Option<&str>,
+    mode: Option<&str>,
+    build: Option<&str>,
+) -> crate::Result<()> {
+    let state = app.state::<State>();
+    let client = state.client().await;
+    let champ = client.search_champion(name);
+    let mode = match mode {
+        Some(mode) => mappings::Mode::from(mode),
+        None => queue::current_mode(&app).await.unwrap_or_default(),
+    };
+    let region = *state.region.read().await;
+    let ranks = rank::preferred_ranks(&app, mode).await;
+    let (overview, role, rank) = client
+        .get_overview(
+            champ,
+            role.map_or_else(|| queue::default_role(mode), mappings::get_role),
+            region,
+            mode,
+            build.map(mappings::Build::from).unwrap_or_default(),
+            &ranks,
+        )
+        .await?;
+    channel.send(KruggMessage::Overview {
+        overview: Box::new(overview),
+        role,
+        rank,
+    })?;
+
+    Ok(())
+}
+
+/// Get the u.gg matchups of the champion `name` in the current region. The
+/// mode defaults to the current queue's. Sends the role and rank bracket the
+/// stats are from.
+#[tauri::command]
+pub async fn get_matchups(
+    app: AppHandle,
+    channel: Channel<KruggMessage>,
+    name: &str,
+    role: Option<&str>,
+    mode: Option<&str>,
+) -> crate::Result<()> {
+    let state = app.state::<State>();
+    let client = state.client().await;
+    let champ = client.search_champion(name);
+    let mode = match mode {
+        Some(mode) => mappings::Mode::from(mode),
+        None => queue::current_mode(&app).await.unwrap_or_default(),
+    };
+    let region = *state.region.read().await;
+    let ranks = rank::preferred_ranks(&app, mode).await;
+    let (matchups, role, rank) = client
+        .get_matchups(
+            champ,
+            role.map_or_else(|| queue::default_role(mode), mappings::get_role),
+            region,
+            mode,
+            &ranks,
+        )
+        .await?;
+    channel.send(KruggMessage::Matchups {
+        matchups: Box::new(matchups),
+        role,
+        rank,
+    })?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_aram_bench(app: AppHandle, channel: Channel<KruggMessage>) -> crate::Result<()> {
+    let ranking = aram::rank_bench(&app).await?;
+    channel.send(KruggMessage::AramBench(ranking))?;
+
+    Ok(())
+}
+
+/// Save the client's current game and input settings to the profile `name`.
+#[tauri::command]
+pub async fn export_settings_profile(app: AppHandle, name: &str) -> crate::Result<()> {
+    profiles::export(&app, name).await
+}
+
+#[tauri::command]
+pub async fn list_settings_profiles(app: AppHandle) -> crate::Result<Vec<String>> {
+    profiles::list(&app).await
+}
+
+#[tauri::command]
+pub async fn diff_settings_profiles(
+    app: AppHandle,
+    left: &str,
+    right: &str,
+) -> crate::Result<Vec<SettingDiff>> {
+    profiles::diff(&app, left, right).await
+}
+
+/// Apply the game and input settings from the profile `name` to the client.
+#[tauri::command]
+pub async fn apply_settings_profile(app: AppHandle, name: &str) -> crate::Result<()> {
+    profiles::apply(&app, name).await
+}
+
+#[tauri::command]
+pub async fn get_loot_advice(app: AppHandle) -> crate::Result<Vec<LootAdvice>> {
+    loot::advise(&app).await
+}
+
+/// Craft a loot item. Only call after the user confirms the action.
+#[tauri::command]
+pub async fn craft_loot(
+    app: AppHandle,
+    loot_id: &str,
+    action: LootAction,
+    repeat: u32,
+) -> crate::Result<()> {
+    loot::craft(&app, loot_id, action, repeat).await
+}
+
+/// Summarize the players in champ select, and the enemy team once the game is
+/// loading.
+#[tauri::command]
+pub async fn scout_players(app: AppHandle) -> crate::Result<Vec<PlayerSummary>> {
+    scout::scout(&app).await
+}
+
+/// Look up a player by their Riot ID, formatted as `name#tag`.
+#[tauri::command]
+pub async fn lookup_player(app: AppHandle, riot_id: &str) -> crate::Result<ProfileBundle> {
+    player::lookup(&app, riot_id).await
+}
+
+/// Get the current summoner's and their friends' presence, with friends
+/// grouped by status.
+#[tauri::command]
+pub async fn get_friends(app: AppHandle) -> crate::Result<FriendsList> {
+    friends::list(&app).await
+}
+
+/// Get the build for the champion the friend with `puuid` is playing.
+#[tauri::command]
+pub async fn get_friend_build(
+    app: AppHandle,
+    channel: Channel<KruggMessage>,
+    puuid: &str,
+) -> crate::Result<()> {
+    let (overview, role, rank) = friends::build(&app, puuid).await?;
+    channel.send(KruggMessage::Overview {
+        overview: Box::new(overview),
+        role,
+        rank,
+    })?;
+
+    Ok(())
+}
+
+/// Get the user's auto pick preferences.
+#[tauri::command]
+pub fn get_auto_pick(app: AppHandle) -> Preferences {
+    champ_select::preferences(&app)
+}
+
+/// Save the user's auto pick preferences. Returns the saved preferences.
+#[tauri::command]
+pub fn set_auto_pick(app: AppHandle, preferences: Preferences) -> crate::Result<Preferences> {
+    champ_select::set_preferences(&app, preferences)
+}
+
+/// Get the region of stats requests and the locale of champion data.
+#[tauri::command]
+pub async fn get_region_locale(app: AppHandle) -> RegionLocale {
+    locale::current(&app).await
+}
+
+/// Change the locale of champion data to `locale`, a ddragon locale like
+/// `en_US`, and save it as the user's setting.
+#[tauri::command]
+pub async fn set_locale(app: AppHandle, locale: &str) -> crate::Result<RegionLocale> {
+    locale::set(&app, locale).await
+}
+
+/// Change the region of stats requests to `region`, an LCU region like `EUW`,
+/// and save it as the user's setting. `None` goes back to the detected region.
+#[tauri::command]
+pub async fn set_region(app: AppHandle, region: Option<&str>) -> crate::Result<RegionLocale> {
+    locale::set_region(&app, region).await
+}
+
+/// Get the current summoner's LP history in `queue_type`, oldest first.
+/// Defaults to ranked solo/duo.
+#[tauri::command]
+pub async fn get_lp_history(
+    app: AppHandle,
+    queue_type: Option<&str>,
+) -> crate::Result<Vec<LpEntry>> {
+    lp::history(&app, queue_type.unwrap_or(rank::SOLO_QUEUE)).await
+}
+
+/// Get the current summoner's win and loss streaks in `queue_type`. Defaults
+/// to ranked solo/duo.
+#[tauri::command]
+pub async fn get_lp_streaks(app: AppHandle, queue_type: Option<&str>) -> crate::Result<LpStreaks> {
+    lp::streaks(&app, queue_type.unwrap_or(rank::SOLO_QUEUE)).await
+}
+
+/// Get the kills, objectives, and buildings of the game with `game_id`, to
+/// seek the replay to.
+#[tauri::command]
+pub async fn get_replay_moments(app: AppHandle, game_id: i64) -> crate::Result<Vec<Moment>> {
+    replay::moments(&app, game_id).await
+}
+
+/// Seek the open replay to `time`, in seconds since the game started.
+#[tauri::command]
+pub async fn replay_seek(app: AppHandle, time: f64) -> crate::Result<Playback> {
+    replay::update_playback(
+        &app,
+        PlaybackUpdate {
+            time: Some(time),
+            ..PlaybackUpdate::default()
+        },
+    )
+    .await
+}
+
+/// Set the playback speed of the open replay.
+#[tauri::command]
+pub async fn replay_set_speed(app: AppHandle, speed: f64) -> crate::Result<Playback> {
+    replay::update_playback(
+        &app,
+        PlaybackUpdate {
+            speed: Some(speed),
+            ..PlaybackUpdate::default()
+        },
+    )
+    .await
+}
+
+/// Pause or resume the open replay.
+#[tauri::command]
+pub async fn replay_set_paused(app: AppHandle, paused: bool) -> crate::Result<Playback> {
+    replay::update_playback(
+        &app,
+        PlaybackUpdate {
+            paused: Some(paused),
+            ..PlaybackUpdate::default()
+        },
+    )
+    .await
+}
+
+/// Lock the camera of the open replay onto the player `name`, or unlock it if
+/// `None`.
+#[tauri::command]
+pub async fn replay_lock_camera(app: AppHandle, name: Option<String>) -> crate::Result<Render> {
+    replay::lock_camera(&app, name).await
+}
+
+/// Get the icon coordinates in the `kind` sprite atlas, whose image is
+/// `ddragon://localhost/atlas/{kind}`.
+#[tauri::command]
+pub async fn get_sprite_atlas(app: AppHandle, kind: AtlasKind) -> crate::Result<AtlasIndex> {
+    atlas::index(&app, kind).await
+}
+
+/// Get the size of the HTTP cache per patch.
+#[tauri::command]
+pub async fn get_cache_usage(app: AppHandle) -> crate::Result<CacheUsage> {
+    cache::usage(&app).await
+}
+
+/// Clear the HTTP cache of `patch`, like `15.7`, or all of it if `None`.
+/// Returns the remaining usage.
+#[tauri::command]
+pub async fn clear_cache(app: AppHandle, patch: Option<String>) -> crate::Result<CacheUsage> {
+    cache::clear(&app, patch).await
+}
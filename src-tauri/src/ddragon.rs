@@ -1,22 +1,40 @@
 #![allow(unused)]
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use ddragon::models::{
     Challenges, Champion, Champions, ChampionsFull, Items, Maps, MissionAssets, ProfileIcons,
     Runes, SpellBuffs, SummonerSpells, Translations,
-    shared::HasImage,
+    shared::{HasImage, Image},
     tft::{self, Arenas, Augments, HeroAugments, Queues, Regalia, Tacticians, Traits},
 };
+use flate2::read::GzDecoder;
 use http_cache_reqwest::{CACacheManager, Cache, CacheMode, HttpCache, HttpCacheOptions};
 use image::DynamicImage;
 use reqwest_middleware::{
     ClientBuilder as MiddlewareClientBuilder, ClientWithMiddleware, RequestBuilder,
 };
 use serde::{Deserialize, de::DeserializeOwned};
+use tar::Archive;
+use tauri::async_runtime::{self, Mutex};
 use tauri_plugin_http::reqwest::{Client as ReqwestClient, Url};
+use tokio::sync::OnceCell;
+
+use crate::{cache, error::DdragonError};
+
+/// Marks a dir a dragontail archive is unpacked into. Empty while unpacking,
+/// then the archive's file name and size, so a different archive is unpacked
+/// again.
+const UNPACKED_MARKER: &str = ".unpacked";
 
-use crate::error::DdragonError;
+/// A sprite sheet, decoded on first use.
+type SpriteSheet = Arc<OnceCell<Arc<DynamicImage>>>;
 
 #[derive(Debug, Clone)]
 pub struct Client {
@@ -24,6 +42,31 @@ pub struct Client {
     version: String,
     base_url: Url,
     locale: String,
+    /// Unpacked dragontail dir to read data and images from instead of
+    /// `base_url`.
+    dragontail: Option<PathBuf>,
+    /// Realm the version is live in, like `na`.
+    realm: Option<String>,
+    /// Versions per data type from the realm, see [`Realm::n`].
+    data_versions: HashMap<String, String>,
+    /// Decoded sprite sheets of the version by file name. Each sheet has its
+    /// own cell, so fetching one doesn't wait for the others.
+    sprites: Arc<Mutex<HashMap<String, SpriteSheet>>>,
+}
+
+/// A region's live versions, from `/realms/{realm}.json`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Realm {
+    /// Versions per data type, e.g. `champion`, `item`, `rune`, `summoner`.
+    pub n: HashMap<String, String>,
+    /// Version of the realm.
+    pub v: String,
+    /// Default locale.
+    pub l: String,
+    pub cdn: String,
+    /// Version of data dragon.
+    pub dd: String,
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +82,9 @@ pub struct ClientBuilder<'a> {
     cache_path: Option<PathBuf>,
     version: Option<&'a str>,
     locale: Option<&'a str>,
+    dragontail: Option<PathBuf>,
+    unpack_dir: Option<PathBuf>,
+    realm: Option<&'a str>,
 }
 
 #[derive(Deserialize)]
@@ -56,6 +102,9 @@ impl<'a> ClientBuilder<'a> {
             cache_path: None,
             version: None,
             locale: None,
+            dragontail: None,
+            unpack_dir: None,
+            realm: None,
         }
     }
 
@@ -89,7 +138,31 @@ impl<'a> ClientBuilder<'a> {
         self
     }
 
-    async fn get(client: &ClientType, url: Url) -> crate::Result<Vec<String>> {
+    /// Use the versions live in `realm`, like `na` or `euw`, instead of the
+    /// newest version. Data dragon can be ahead of a region on patch day.
+    /// Ignored if the realm can't be fetched.
+    pub const fn realm(mut self, realm: &'a str) -> Self {
+        self.realm = Some(realm);
+        self
+    }
+
+    /// Load data and images from a dragontail `.tgz` or an unpacked dragontail
+    /// dir instead of over HTTP. Archives are unpacked into
+    /// [`Self::unpack_dir`]. The version defaults to the newest one in the
+    /// dragontail.
+    pub fn dragontail<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.dragontail = Some(path.into());
+        self
+    }
+
+    /// Dir to unpack a dragontail archive into, defaults to a `dragontail` dir
+    /// next to the archive.
+    pub fn unpack_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.unpack_dir = Some(dir.into());
+        self
+    }
+
+    async fn get<T: DeserializeOwned>(client: &ClientType, url: Url) -> crate::Result<T> {
         Ok(match client {
             ClientType::Middleware(client) => client.get(url).send().await?.json().await?,
             ClientType::Plain(client) => client.get(url).send().await?.json().await?,
@@ -98,17 +171,30 @@ impl<'a> ClientBuilder<'a> {
 
     /// Builds the [`Client`]. Adds caching middleware if a
     /// [`ClientType::Plain`] client was provided with `cache_path`.
-    pub async fn build(self) -> crate::Result<Client> {
-        let client = if let Some(client) = self.client {
+    pub async fn build(mut self) -> crate::Result<Client> {
+        let client = if let Some(client) = self.client.take() {
             client
         } else {
             // TODO: zstd https://github.com/tauri-apps/plugins-workspace/pull/2561
             ClientType::Plain(ReqwestClient::builder().brotli(true).build()?)
         };
         let base_url = Url::parse(self.base_url)?;
-        let mut versions = Self::get(&client, base_url.join("/api/versions.json")?).await?;
-        let version = match self.version {
-            Some(v) if versions.iter().any(|version| version == v) => v.to_string(),
+        if let Some(path) = self.dragontail.clone() {
+            return self.build_dragontail(client, base_url, path).await;
+        }
+        let mut versions =
+            Self::get::<Vec<String>>(&client, base_url.join("/api/versions.json")?).await?;
+        let realm = match self.realm {
+            Some(realm) => {
+                Self::get::<Realm>(&client, base_url.join(&format!("/realms/{realm}.json"))?)
+                    .await
+                    .ok()
+            }
+            None => None,
+        };
+        let version = match (self.version, &realm) {
+            (Some(v), _) if versions.iter().any(|version| version == v) => v.to_string(),
+            (_, Some(realm)) if versions.contains(&realm.v) => realm.v.clone(),
             _ => {
                 if versions.is_empty() {
                     return Err(DdragonError::NoLatestVersion.into());
@@ -118,9 +204,13 @@ impl<'a> ClientBuilder<'a> {
                 }
             }
         };
+        let data_versions = realm
+            .filter(|realm| realm.v == version)
+            .map(|realm| realm.n)
+            .unwrap_or_default();
         let locale = match self.locale {
             Some(l)
-                if Self::get(&client, base_url.join("/cdn/languages.json")?)
+                if Self::get::<Vec<String>>(&client, base_url.join("/cdn/languages.json")?)
                     .await?
                     .iter()
                     .any(|lang| lang == l) =>
@@ -129,29 +219,149 @@ impl<'a> ClientBuilder<'a> {
             }
             _ => "en_US",
         };
-        let middleware_client = match client {
+
+        Ok(Client {
+            client: Self::middleware_client(client, self.cache_path),
+            version,
+            base_url,
+            locale: locale.to_owned(),
+            dragontail: None,
+            realm: self.realm.map(str::to_owned),
+            data_versions,
+            sprites: Arc::default(),
+        })
+    }
+
+    pub(crate) fn middleware_client(
+        client: ClientType,
+        cache_path: Option<PathBuf>,
+    ) -> ClientWithMiddleware {
+        match client {
             ClientType::Middleware(client) => client,
-            ClientType::Plain(client) => match self.cache_path {
+            ClientType::Plain(client) => match cache_path {
                 Some(path) => MiddlewareClientBuilder::new(client)
                     .with(Cache(HttpCache {
                         mode: CacheMode::ForceCache,
                         manager: CACacheManager { path },
-                        options: HttpCacheOptions::default(),
+                        options: HttpCacheOptions {
+                            cache_mode_fn: Some(Arc::new(cache::mode)),
+                            ..HttpCacheOptions::default()
+                        },
                     }))
                     .build(),
                 None => MiddlewareClientBuilder::new(client).build(),
             },
+        }
+    }
+
+    /// Build a client that reads from the dragontail at `path`, without any
+    /// requests.
+    async fn build_dragontail(
+        self,
+        client: ClientType,
+        base_url: Url,
+        path: PathBuf,
+    ) -> crate::Result<Client> {
+        let dir = if is_dir(&path).await {
+            path
+        } else {
+            let dir = self
+                .unpack_dir
+                .clone()
+                .unwrap_or_else(|| path.with_file_name("dragontail"));
+            let target = dir.clone();
+            async_runtime::spawn_blocking(move || unpack(&path, &target))
+                .await
+                .map_err(|err| io::Error::other(err.to_string()))??;
+            dir
+        };
+
+        let mut versions = dragontail_versions(&dir).await?;
+        let version = match self.version {
+            Some(v) if versions.iter().any(|version| version == v) => v.to_owned(),
+            _ if versions.is_empty() => {
+                return Err(DdragonError::EmptyDragontail(dir.display().to_string()).into());
+            }
+            _ => versions.swap_remove(0),
+        };
+        let data_dir = dir.join(&version).join("data");
+        let locale = match self.locale {
+            Some(l) if is_dir(&data_dir.join(l)).await => l,
+            _ => "en_US",
         };
 
         Ok(Client {
-            client: middleware_client,
+            client: Self::middleware_client(client, self.cache_path),
             version,
             base_url,
             locale: locale.to_owned(),
+            dragontail: Some(dir),
+            realm: None,
+            data_versions: HashMap::new(),
+            sprites: Arc::default(),
         })
     }
 }
 
+async fn is_dir(path: &Path) -> bool {
+    tokio::fs::metadata(path)
+        .await
+        .is_ok_and(|metadata| metadata.is_dir())
+}
+
+/// Versions in an unpacked dragontail dir, newest first.
+async fn dragontail_versions(dir: &Path) -> io::Result<Vec<String>> {
+    let parse = |version: &str| {
+        version
+            .split('.')
+            .map(str::parse::<u32>)
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+    };
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    let mut versions = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        if let Ok(name) = entry.file_name().into_string()
+            && let Some(version) = parse(&name)
+        {
+            versions.push((version, name));
+        }
+    }
+    versions.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+
+    Ok(versions.into_iter().map(|(_, name)| name).collect())
+}
+
+/// Unpack the dragontail `.tgz` at `archive` into `dir`, unless it was
+/// already unpacked there.
+fn unpack(archive: &Path, dir: &Path) -> io::Result<()> {
+    let marker = dir.join(UNPACKED_MARKER);
+    let id = format!(
+        "{} {}",
+        archive.file_name().unwrap_or_default().to_string_lossy(),
+        fs::metadata(archive)?.len()
+    );
+    if fs::read_to_string(&marker).is_ok_and(|unpacked| unpacked == id) {
+        return Ok(());
+    }
+
+    if dir.exists() {
+        // Only replace dirs unpacked into before, the marker is written first.
+        if !marker.exists() && fs::read_dir(dir)?.next().is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and wasn't unpacked by krugg", dir.display()),
+            ));
+        }
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(dir)?;
+    fs::write(&marker, "")?;
+    // `unpack` keeps entries inside `dir`.
+    Archive::new(GzDecoder::new(File::open(archive)?)).unpack(dir)?;
+    fs::write(marker, id)
+}
+
 macro_rules! impl_endpoints {
     ( $($name:ident : $path:literal, $t:ty ),* $(,)? ) => {
         $(
@@ -183,16 +393,111 @@ impl Client {
         self.locale.as_str()
     }
 
-    fn url(&self) -> crate::Result<Url> {
+    /// Realm the version is live in, see [`ClientBuilder::realm`].
+    pub fn realm(&self) -> Option<&str> {
+        self.realm.as_deref()
+    }
+
+    /// Whether data is read from a local dragontail instead of over HTTP.
+    pub const fn is_local(&self) -> bool {
+        self.dragontail.is_some()
+    }
+
+    /// Available versions, newest first.
+    pub async fn get_versions(&self) -> crate::Result<Vec<String>> {
+        match &self.dragontail {
+            Some(dir) => Ok(dragontail_versions(dir).await?),
+            None => Ok(self
+                .client
+                .get(self.base_url.join("/api/versions.json")?)
+                .send()
+                .await?
+                .json()
+                .await?),
+        }
+    }
+
+    /// The version live in the client's realm, or the newest version without
+    /// one.
+    pub async fn get_live_version(&self) -> crate::Result<String> {
+        if let Some(realm) = &self.realm
+            && self.dragontail.is_none()
+        {
+            let realm = self
+                .client
+                .get(self.base_url.join(&format!("/realms/{realm}.json"))?)
+                .send()
+                .await?
+                .json::<Realm>()
+                .await?;
+            return Ok(realm.v);
+        }
+
         Ok(self
-            .base_url
-            .join(&format!("/cdn/{}/data/{}/", self.version, self.locale))?)
+            .get_versions()
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(DdragonError::NoLatestVersion)?)
     }
 
+    /// Available locales, like `en_US`.
+    pub async fn get_languages(&self) -> crate::Result<Vec<String>> {
+        match &self.dragontail {
+            Some(dir) => Ok(serde_json::from_slice(
+                &tokio::fs::read(dir.join("languages.json")).await?,
+            )?),
+            None => Ok(self
+                .client
+                .get(self.base_url.join("/cdn/languages.json")?)
+                .send()
+                .await?
+                .json()
+                .await?),
+        }
+    }
+
+    /// Version of a data file or image group like `champion` or `spell`,
+    /// from the realm if it has one.
+    fn data_version(&self, kind: &str) -> &str {
+        let kind = match kind {
+            "championFull" | "passive" => "champion",
+            "runesReforged" => "rune",
+            "spell" => "summoner",
+            kind => kind,
+        };
+        self.data_versions
+            .get(kind)
+            .map_or(self.version.as_str(), String::as_str)
+    }
+
+    fn url(&self, kind: &str) -> crate::Result<Url> {
+        Ok(self.base_url.join(&format!(
+            "/cdn/{}/data/{}/",
+            self.data_version(kind),
+            self.locale
+        ))?)
+    }
+
+    /// Get a data file by its path like `./champion.json`.
     async fn get<T: DeserializeOwned>(&self, path: &str) -> crate::Result<T> {
+        if let Some(dir) = &self.dragontail {
+            let path = dir
+                .join(&self.version)
+                .join("data")
+                .join(&self.locale)
+                .join(path);
+            return Ok(serde_json::from_slice(&tokio::fs::read(path).await?)?);
+        }
+
+        let kind = path
+            .trim_start_matches("./")
+            .split(['/', '.'])
+            .next()
+            .unwrap_or_default();
         Ok(self
             .client
-            .get(self.url()?.join(path)?)
+            .get(self.url(kind)?.join(path)?)
             .send()
             .await?
             .json::<T>()
@@ -232,26 +537,71 @@ impl Client {
             .ok_or_else(|| DdragonError::NoChampionData(id.to_owned()))?)
     }
 
-    async fn get_image(&self, path: Url) -> crate::Result<DynamicImage> {
-        let response = self.client.get(path.as_str()).send().await?;
+    /// Get the encoded image at its path under `/cdn/{version}/img/`.
+    async fn get_image_bytes(&self, path: &str) -> crate::Result<Vec<u8>> {
+        if let Some(dir) = &self.dragontail {
+            let path = dir.join(&self.version).join("img").join(path);
+            return Ok(tokio::fs::read(path).await?);
+        }
+
+        let kind = path.split('/').next().unwrap_or_default();
+        let url = self
+            .base_url
+            .join(&format!("/cdn/{}/img/{path}", self.data_version(kind)))?;
+        let response = self.client.get(url.as_str()).send().await?;
+        Ok(response.bytes().await?.into())
+    }
+
+    /// Get an image by its path under `/cdn/{version}/img/`.
+    async fn get_image(&self, path: &str) -> crate::Result<DynamicImage> {
+        Ok(image::load_from_memory(&self.get_image_bytes(path).await?)?)
+    }
+
+    /// Get an unversioned image by its path under `/cdn/img/`, like rune icons
+    /// and champion splashes.
+    pub async fn get_cdn_image(&self, path: &str) -> crate::Result<DynamicImage> {
+        if let Some(dir) = &self.dragontail {
+            return Ok(image::load_from_memory(
+                &tokio::fs::read(dir.join("img").join(path)).await?,
+            )?);
+        }
+
+        let url = self.base_url.join(&format!("/cdn/img/{path}"))?;
+        let response = self.client.get(url.as_str()).send().await?;
         Ok(image::load_from_memory(&response.bytes().await?)?)
     }
 
     pub async fn get_image_of<T: HasImage + Sync>(&self, item: &T) -> crate::Result<DynamicImage> {
-        self.get_image(self.base_url.join(&format!(
-            "/cdn/{}/img/{}",
-            &self.version,
-            item.image_path()
-        ))?)
-        .await
+        self.get_image(&item.image_path()).await
     }
 
-    pub async fn get_sprite_of<T: HasImage + Sync>(&self, item: &T) -> crate::Result<DynamicImage> {
-        self.get_image(self.base_url.join(&format!(
-            "/cdn/{}/img/{}",
-            &self.version,
-            item.sprite_path()
-        ))?)
-        .await
+    /// Get the decoded sprite sheet `sprite`, like `champion0.png`. Each sheet
+    /// is downloaded once, and decoded off the async runtime.
+    pub async fn get_sprite_sheet(&self, sprite: &str) -> crate::Result<Arc<DynamicImage>> {
+        let cell = Arc::clone(
+            self.sprites
+                .lock()
+                .await
+                .entry(sprite.to_owned())
+                .or_default(),
+        );
+        let sheet = cell
+            .get_or_try_init(|| async {
+                let bytes = self.get_image_bytes(&format!("sprite/{sprite}")).await?;
+                let sheet = async_runtime::spawn_blocking(move || image::load_from_memory(&bytes))
+                    .await??;
+                Ok::<_, crate::Error>(Arc::new(sheet))
+            })
+            .await?;
+
+        Ok(Arc::clone(sheet))
+    }
+
+    /// Get the icon of `image` cropped from its sprite sheet.
+    pub async fn get_sprite_of(&self, image: &Image) -> crate::Result<DynamicImage> {
+        let sheet = self.get_sprite_sheet(&image.sprite).await?;
+        let [x, y, w, h] =
+            [image.x, image.y, image.w, image.h].map(|n| u32::try_from(n).unwrap_or_default());
+        Ok(sheet.crop_imm(x, y, w, h))
     }
 }
@@ -4,6 +4,12 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Cache(#[from] cacache::Error),
     #[error(transparent)]
     Image(#[from] image::ImageError),
     #[error(transparent)]
@@ -23,12 +29,28 @@ pub enum Error {
     Ddragon(#[from] DdragonError),
     #[error(transparent)]
     Ugg(#[from] UggError),
+    #[error(transparent)]
+    Aram(#[from] AramError),
+    #[error(transparent)]
+    Profile(#[from] ProfileError),
+    #[error(transparent)]
+    Loot(#[from] LootError),
+    #[error(transparent)]
+    Scout(#[from] ScoutError),
+    #[error(transparent)]
+    Friends(#[from] FriendsError),
+    #[error(transparent)]
+    Locale(#[from] LocaleError),
+    #[error(transparent)]
+    Protocol(#[from] ProtocolError),
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum DdragonError {
     #[error("Failed to get the latest API version")]
     NoLatestVersion,
+    #[error("No versions in the dragontail at: {0}")]
+    EmptyDragontail(String),
     #[error("Failed to get champion data for id: {0}")]
     NoChampionData(String),
 }
@@ -43,6 +65,58 @@ pub enum UggError {
     MissingRole,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum AramError {
+    #[error("Champ select session has no bench")]
+    NoBench,
+    #[error("Failed to find the local player in champ select")]
+    NoLocalPlayer,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileError {
+    #[error("Invalid settings profile name: {0}")]
+    InvalidName(String),
+    #[error("Settings profile not found: {0}")]
+    NotFound(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LootError {
+    #[error("No matching loot recipe for: {0}")]
+    NoRecipe(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScoutError {
+    #[error("No players to scout")]
+    NoPlayers,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FriendsError {
+    #[error("Friend not found: {0}")]
+    NotFound(String),
+    #[error("Friend is not playing a champion")]
+    NoChampion,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocaleError {
+    #[error("Unsupported locale: {0}")]
+    Unsupported(String),
+    #[error("Unsupported region: {0}")]
+    UnsupportedRegion(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProtocolError {
+    #[error("Image not found: {0}")]
+    NotFound(String),
+    #[error("Invalid image query: {0}")]
+    InvalidQuery(String),
+}
+
 macro_rules! impl_serialize_err {
     ( $($ty:ty),* $(,)? ) => {
         $(
@@ -58,4 +132,11 @@ macro_rules! impl_serialize_err {
     };
 }
 
-impl_serialize_err![Error, DdragonError, UggError];
+impl_serialize_err![
+    Error,
+    DdragonError,
+    UggError,
+    AramError,
+    ProfileError,
+    LootError
+];
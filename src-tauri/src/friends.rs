@@ -0,0 +1,172 @@
+//! Friends list with live presence.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime, async_runtime};
+use tauri_plugin_lcu::{LcuExt, models::chat::Presence};
+use tokio::time::{self, Duration};
+use ugg_types::{mappings, overview::Overview};
+
+use crate::{State, error::FriendsError, queue, rank};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FriendStatus {
+    InGame,
+    ChampSelect,
+    InQueue,
+    Online,
+    Away,
+    Mobile,
+    Offline,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FriendSummary {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub status: FriendStatus,
+    pub status_message: String,
+    pub champion_id: Option<i64>,
+    pub champion_name: Option<String>,
+    pub queue_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FriendsList {
+    pub me: FriendSummary,
+    pub friends: BTreeMap<FriendStatus, Vec<FriendSummary>>,
+}
+
+fn status(presence: &impl Presence, availability: &str) -> FriendStatus {
+    match (availability, presence.game_status()) {
+        ("offline", _) => FriendStatus::Offline,
+        ("mobile", _) => FriendStatus::Mobile,
+        (_, Some("inGame")) => FriendStatus::InGame,
+        (_, Some("championSelect")) => FriendStatus::ChampSelect,
+        (_, Some("inQueue")) => FriendStatus::InQueue,
+        ("away", _) => FriendStatus::Away,
+        _ => FriendStatus::Online,
+    }
+}
+
+/// Get the current summoner's and their friends' presence, with friends
+/// grouped by status.
+pub async fn list<R: Runtime>(app: &AppHandle<R>) -> crate::Result<FriendsList> {
+    let lcu = app.lcu();
+    let state = app.state::<State>();
+    let client = state.client().await;
+    let champion_name = |id: Option<i64>| {
+        id.and_then(|id| client.champion_by_key(id))
+            .map(|champ| champ.name.clone())
+    };
+
+    let me = lcu.get_chat_me().await?;
+    let me = FriendSummary {
+        status: status(&me, &me.availability),
+        champion_id: me.champion_id(),
+        champion_name: champion_name(me.champion_id()),
+        queue_id: me.queue_id(),
+        puuid: me.puuid,
+        game_name: me.game_name,
+        tag_line: me.game_tag,
+        status_message: me.status_message,
+    };
+
+    let mut friends = BTreeMap::<_, Vec<_>>::new();
+    for friend in lcu.get_friends().await? {
+        let summary = FriendSummary {
+            status: status(&friend, &friend.availability),
+            champion_id: friend.champion_id(),
+            champion_name: champion_name(friend.champion_id()),
+            queue_id: friend.queue_id(),
+            puuid: friend.puuid,
+            game_name: friend.game_name,
+            tag_line: friend.game_tag,
+            status_message: friend.status_message,
+        };
+        friends.entry(summary.status).or_default().push(summary);
+    }
+    for group in friends.values_mut() {
+        group.sort_by_key(|friend| friend.game_name.to_lowercase());
+    }
+
+    Ok(FriendsList { me, friends })
+}
+
+/// Get the u.gg overview for the champion the friend with `puuid` is playing.
+pub async fn build<R: Runtime>(
+    app: &AppHandle<R>,
+    puuid: &str,
+) -> crate::Result<(Overview, mappings::Role, mappings::Rank)> {
+    let friend = app
+        .lcu()
+        .get_friends()
+        .await?
+        .into_iter()
+        .find(|friend| friend.puuid == puuid)
+        .ok_or_else(|| FriendsError::NotFound(puuid.to_owned()))?;
+    let state = app.state::<State>();
+    let client = state.client().await;
+    let champ = friend
+        .champion_id()
+        .and_then(|id| client.champion_by_key(id))
+        .ok_or(FriendsError::NoChampion)?;
+    let mode = friend
+        .queue_id()
+        .and_then(|queue_id| queue::mode(queue_id, 0, ""))
+        .unwrap_or_default();
+    let role = queue::default_role(mode);
+
+    let region = *state.region.read().await;
+    let ranks = rank::preferred_ranks(app, mode).await;
+
+    client
+        .get_overview(
+            champ,
+            role,
+            region,
+            mode,
+            mappings::Build::Recommended,
+            &ranks,
+        )
+        .await
+}
+
+/// Emit `friends-changed` with the friends list whenever a friend's presence
+/// changes.
+pub fn watch<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<State>();
+    let cancel_token = state.cancel_token.clone();
+    let app = app.clone();
+
+    async_runtime::spawn(state.tracker.track_future(async move {
+        let mut interval = time::interval(POLL_INTERVAL);
+        let mut last_seen = None;
+
+        loop {
+            tokio::select! {
+                biased;
+                () = cancel_token.cancelled() => break,
+                _ = interval.tick() => {
+                    if !app.lcu().connected().await {
+                        last_seen = None;
+                        continue;
+                    }
+                    if let Ok(friends) = list(&app).await
+                        && last_seen.as_ref() != Some(&friends)
+                    {
+                        _ = app.emit("friends-changed", &friends);
+                        last_seen = Some(friends);
+                    }
+                }
+            }
+        }
+    }));
+}
@@ -2,22 +2,42 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../../README.md")]
 
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use mimalloc::MiMalloc;
 use tauri::{
-    App, AppHandle, Listener, Manager, RunEvent, Runtime, async_runtime, tray::TrayIconBuilder,
+    App, AppHandle, Listener, Manager, RunEvent, Runtime,
+    async_runtime::{self, RwLock},
+    tray::TrayIconBuilder,
 };
 use tauri_plugin_autostart::MacosLauncher;
 use tauri_plugin_prevent_default::Flags;
 use tauri_plugin_store::StoreExt;
 use tokio::task;
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
+use ugg_types::mappings;
 
+mod aram;
+mod atlas;
+mod cache;
+mod cdragon;
+mod champ_select;
 mod channel;
 mod commands;
 mod ddragon;
 mod error;
+mod friends;
+mod locale;
+mod loot;
+mod lp;
+mod patch;
+mod player;
+mod profiles;
+mod protocol;
+mod queue;
+mod rank;
+mod replay;
+mod scout;
 mod ugg;
 
 use error::{Error, Result};
@@ -25,17 +45,35 @@ use error::{Error, Result};
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
 const STORE_FILE: &str = "app_data.json";
-const EVENTS: &[&str] = &["lcu-connected", "lcu-lockfile", "lcu-base-url"];
+const EVENTS: &[&str] = &[
+    "lcu-connected",
+    "lcu-lockfile",
+    "lcu-base-url",
+    "aram-bench",
+    "friends-changed",
+    "locale-changed",
+    "patch-changed",
+];
 
 #[derive(Debug)]
 struct State {
-    client: ugg::Client,
+    /// Swapped when the locale changes; requests keep the client they started
+    /// with.
+    client: RwLock<Arc<ugg::Client>>,
+    /// u.gg region of stats requests, see [`locale`].
+    region: RwLock<mappings::Region>,
     /// Used to cancel all tasks before the app exits.
     cancel_token: CancellationToken,
     /// Used to wait for all tasks to complete before the app exits.
     tracker: TaskTracker,
 }
 
+impl State {
+    async fn client(&self) -> Arc<ugg::Client> {
+        Arc::clone(&*self.client.read().await)
+    }
+}
+
 pub fn run() {
     #[allow(clippy::large_stack_frames)] // generate_context macro is scuffed
     tauri::Builder::default()
@@ -43,6 +81,37 @@ pub fn run() {
             commands::show_main_window,
             commands::get_champions,
             commands::get_champion,
+            commands::get_champion_details,
+            commands::get_perks,
+            commands::get_arena_augments,
+            commands::get_overview,
+            commands::get_matchups,
+            commands::get_aram_bench,
+            commands::export_settings_profile,
+            commands::list_settings_profiles,
+            commands::diff_settings_profiles,
+            commands::apply_settings_profile,
+            commands::get_loot_advice,
+            commands::craft_loot,
+            commands::scout_players,
+            commands::lookup_player,
+            commands::get_friends,
+            commands::get_friend_build,
+            commands::get_region_locale,
+            commands::get_auto_pick,
+            commands::set_auto_pick,
+            commands::set_locale,
+            commands::set_region,
+            commands::get_lp_history,
+            commands::get_lp_streaks,
+            commands::get_replay_moments,
+            commands::replay_seek,
+            commands::replay_set_speed,
+            commands::replay_set_paused,
+            commands::replay_lock_camera,
+            commands::get_sprite_atlas,
+            commands::get_cache_usage,
+            commands::clear_cache,
         ])
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
@@ -66,6 +135,7 @@ pub fn run() {
         )
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_lcu::init(STORE_FILE))
+        .register_asynchronous_uri_scheme_protocol(protocol::SCHEME, protocol::handle)
         .setup(setup)
         .build(tauri::generate_context!())
         .expect("error while running the application")
@@ -74,12 +144,25 @@ pub fn run() {
 
 fn setup<R: Runtime>(app: &mut App<R>) -> std::result::Result<(), Box<dyn std::error::Error>> {
     let app = app.handle();
+
+    // Set up persistent store.
+    app.store_builder(STORE_FILE)
+        .auto_save(Duration::from_secs(15 * 60))
+        .build()?;
+
     task::block_in_place(move || {
         async_runtime::block_on(async {
             // Set up app state.
+            app.manage(protocol::ImageCache::default());
+            app.manage(atlas::AtlasCache::default());
+            let region = locale::initial_region(app);
+            let mut client = ugg::ClientBuilder::new().locale(locale::initial_locale(app));
+            if let Some(realm) = locale::ddragon_realm(region) {
+                client = client.realm(realm);
+            }
             app.manage(State {
-                // TODO: sys locale
-                client: ugg::ClientBuilder::new().build(app).await?,
+                client: RwLock::new(Arc::new(client.build(app).await?)),
+                region: RwLock::new(region),
                 cancel_token: CancellationToken::new(),
                 tracker: TaskTracker::new(),
             });
@@ -87,11 +170,12 @@ fn setup<R: Runtime>(app: &mut App<R>) -> std::result::Result<(), Box<dyn std::e
             Ok::<(), crate::Error>(())
         })
     })?;
-
-    // Set up persistent store.
-    app.store_builder(STORE_FILE)
-        .auto_save(Duration::from_secs(15 * 60))
-        .build()?;
+    aram::watch(app);
+    champ_select::watch(app);
+    friends::watch(app);
+    locale::watch(app);
+    lp::watch(app);
+    patch::watch(app);
 
     // TODO: Tray-relative window positioning.
     TrayIconBuilder::new()
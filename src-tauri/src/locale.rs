@@ -0,0 +1,313 @@
+//! Region and locale detection.
+//!
+//! Each is resolved in order from the user's setting in the store, the
+//! running League client, and the OS locale.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime, async_runtime};
+use tauri_plugin_lcu::LcuExt;
+use tauri_plugin_store::{JsonValue, StoreExt};
+use tokio::time::{self, Duration};
+use ugg_types::mappings::Region;
+
+use crate::{STORE_FILE, State, error::LocaleError, patch};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Store key of the user's region setting, an LCU region like `EUW`.
+const REGION_KEY: &str = "region";
+/// Store key of the user's locale setting, a ddragon locale like `en_US`.
+const LOCALE_KEY: &str = "locale";
+/// Store key of the region last read from the League client.
+const CLIENT_REGION_KEY: &str = "client_region";
+/// Store key of the locale last read from the League client.
+const CLIENT_LOCALE_KEY: &str = "client_locale";
+
+pub const DEFAULT_LOCALE: &str = "en_US";
+/// Locales with ddragon translations.
+const DDRAGON_LOCALES: &[&str] = &[
+    "en_US", "cs_CZ", "de_DE", "el_GR", "en_AU", "en_GB", "en_PH", "en_SG", "es_AR", "es_ES",
+    "es_MX", "fr_FR", "hu_HU", "it_IT", "ja_JP", "ko_KR", "pl_PL", "pt_BR", "ro_RO", "ru_RU",
+    "th_TH", "tr_TR", "vi_VN", "zh_CN", "zh_MY", "zh_TW",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionLocale {
+    pub region: Region,
+    pub locale: String,
+}
+
+/// Convert an LCU or OS locale like `en_US`, `en-US`, or `en_US.UTF-8` to a
+/// ddragon locale. Falls back to the first ddragon locale with the same
+/// language.
+pub fn ddragon_locale(locale: &str) -> Option<&'static str> {
+    let locale = locale.split(['.', '@']).next()?.replace('-', "_");
+    let language = locale.split('_').next()?;
+
+    DDRAGON_LOCALES
+        .iter()
+        .find(|l| l.eq_ignore_ascii_case(&locale))
+        .or_else(|| {
+            DDRAGON_LOCALES
+                .iter()
+                .find(|l| l.split('_').next() == Some(language))
+        })
+        .copied()
+}
+
+/// Convert an LCU region like `EUW` or a platform ID like `EUW1` to a u.gg
+/// region. Returns `None` for regions without u.gg stats, e.g. `PBE`.
+pub fn lcu_region(region: &str) -> Option<Region> {
+    Some(match region.to_ascii_uppercase().as_str() {
+        "NA" | "NA1" => Region::NA1,
+        "EUW" | "EUW1" => Region::EUW1,
+        "EUNE" | "EUN1" => Region::EUN1,
+        "KR" => Region::KR,
+        "BR" | "BR1" => Region::BR1,
+        "LAN" | "LA1" => Region::LA1,
+        "LAS" | "LA2" => Region::LA2,
+        "OCE" | "OC1" => Region::OC1,
+        "RU" => Region::RU,
+        "TR" | "TR1" => Region::TR1,
+        "JP" | "JP1" => Region::JP1,
+        "PH" | "PH2" => Region::PH2,
+        "SG" | "SG2" => Region::SG2,
+        "TH" | "TH2" => Region::TH2,
+        "TW" | "TW2" => Region::TW2,
+        "VN" | "VN2" => Region::VN2,
+        "ME" | "ME1" => Region::ME1,
+        _ => return None,
+    })
+}
+
+/// ddragon realm of a u.gg region, see [`ddragon::ClientBuilder::realm`].
+///
+/// [`ddragon::ClientBuilder::realm`]: crate::ddragon::ClientBuilder::realm
+pub const fn ddragon_realm(region: Region) -> Option<&'static str> {
+    Some(match region {
+        Region::NA1 => "na",
+        Region::EUW1 => "euw",
+        Region::EUN1 => "eune",
+        Region::KR => "kr",
+        Region::BR1 => "br",
+        Region::LA1 => "lan",
+        Region::LA2 => "las",
+        Region::OC1 => "oce",
+        Region::RU => "ru",
+        Region::TR1 => "tr",
+        Region::JP1 => "jp",
+        Region::PH2 => "ph",
+        Region::SG2 => "sg",
+        Region::TH2 => "th",
+        Region::TW2 => "tw",
+        Region::VN2 => "vn",
+        Region::ME1 => "me",
+        Region::World => return None,
+    })
+}
+
+/// Guess the u.gg region from the country of an OS locale like `en-US`.
+fn locale_region(locale: &str) -> Option<Region> {
+    let country = locale.split(['.', '@']).next()?.split(['_', '-']).nth(1)?;
+
+    Some(match country.to_ascii_uppercase().as_str() {
+        "US" | "CA" => Region::NA1,
+        "GB" | "IE" | "DE" | "AT" | "CH" | "FR" | "BE" | "NL" | "LU" | "ES" | "PT" | "IT"
+        | "DK" | "SE" | "NO" | "FI" => Region::EUW1,
+        "PL" | "CZ" | "SK" | "HU" | "RO" | "BG" | "GR" | "HR" | "SI" | "EE" | "LV" | "LT" => {
+            Region::EUN1
+        }
+        "KR" => Region::KR,
+        "BR" => Region::BR1,
+        "MX" | "CO" | "PE" | "VE" | "EC" | "GT" | "CR" | "PA" => Region::LA1,
+        "AR" | "CL" | "UY" | "PY" | "BO" => Region::LA2,
+        "AU" | "NZ" => Region::OC1,
+        "RU" => Region::RU,
+        "TR" => Region::TR1,
+        "JP" => Region::JP1,
+        "PH" => Region::PH2,
+        "SG" | "MY" => Region::SG2,
+        "TH" => Region::TH2,
+        "TW" | "HK" => Region::TW2,
+        "VN" => Region::VN2,
+        "AE" | "SA" | "EG" | "QA" | "KW" => Region::ME1,
+        _ => return None,
+    })
+}
+
+fn store_string<R: Runtime>(app: &AppHandle<R>, key: &str) -> Option<String> {
+    match app.store(STORE_FILE).ok()?.get(key) {
+        Some(JsonValue::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+/// The user's locale setting, if any.
+fn locale_setting<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    store_string(app, LOCALE_KEY)
+}
+
+/// The user's region setting, if any.
+fn region_setting<R: Runtime>(app: &AppHandle<R>) -> Option<Region> {
+    store_string(app, REGION_KEY).and_then(|region| lcu_region(&region))
+}
+
+/// The ddragon locale to build the u.gg client with.
+pub fn initial_locale<R: Runtime>(app: &AppHandle<R>) -> &'static str {
+    store_string(app, LOCALE_KEY)
+        .or_else(|| store_string(app, CLIENT_LOCALE_KEY))
+        .or_else(sys_locale::get_locale)
+        .and_then(|locale| ddragon_locale(&locale))
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// The u.gg region to use until the League client is connected.
+pub fn initial_region<R: Runtime>(app: &AppHandle<R>) -> Region {
+    region_setting(app)
+        .or_else(|| store_string(app, CLIENT_REGION_KEY).and_then(|region| lcu_region(&region)))
+        .or_else(|| sys_locale::get_locale().and_then(|locale| locale_region(&locale)))
+        .unwrap_or_default()
+}
+
+/// Read the region and locale from the League client once it's connected.
+/// Each replaces the current one unless the user set one, and both are saved
+/// as defaults for the next launch.
+pub fn watch<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<State>();
+    let cancel_token = state.cancel_token.clone();
+    let app = app.clone();
+
+    async_runtime::spawn(state.tracker.track_future(async move {
+        let mut interval = time::interval(POLL_INTERVAL);
+        let mut detected = false;
+
+        loop {
+            tokio::select! {
+                biased;
+                () = cancel_token.cancelled() => break,
+                _ = interval.tick() => {
+                    if !app.lcu().connected().await {
+                        detected = false;
+                        continue;
+                    }
+                    if detected {
+                        continue;
+                    }
+                    let Ok(client) = app.lcu().get_region_locale().await else {
+                        continue;
+                    };
+                    detected = true;
+
+                    if let Ok(store) = app.store(STORE_FILE) {
+                        store.set(CLIENT_REGION_KEY, client.region.as_str());
+                        store.set(CLIENT_LOCALE_KEY, client.locale.as_str());
+                    }
+                    if region_setting(&app).is_none()
+                        && let Some(region) = lcu_region(&client.region)
+                    {
+                        *app.state::<State>().region.write().await = region;
+                        // Switch to the versions live in the region.
+                        _ = patch::update(&app).await;
+                    }
+                    if locale_setting(&app).is_none()
+                        && let Some(locale) = ddragon_locale(&client.locale)
+                        && app.state::<State>().client().await.ddragon().locale() != locale
+                    {
+                        _ = apply(&app, locale).await;
+                    }
+                }
+            }
+        }
+    }));
+}
+
+/// The current region and the locale of the u.gg client.
+pub async fn current<R: Runtime>(app: &AppHandle<R>) -> RegionLocale {
+    let state = app.state::<State>();
+    let client = state.client().await;
+    RegionLocale {
+        region: *state.region.read().await,
+        locale: client.ddragon().locale().to_owned(),
+    }
+}
+
+/// Rebuild the u.gg client's data in `locale`, a ddragon locale like `en_US`,
+/// and save it as the user's setting. Emits `locale-changed` with the new
+/// region and locale.
+pub async fn set<R: Runtime>(app: &AppHandle<R>, locale: &str) -> crate::Result<RegionLocale> {
+    let current = apply(app, locale).await?;
+    app.store(STORE_FILE)?.set(LOCALE_KEY, locale);
+    Ok(current)
+}
+
+/// Set the region of stats requests to `region`, an LCU region like `EUW`, and
+/// save it as the user's setting. `None` clears the setting and goes back to
+/// the League client's or OS region. Emits `locale-changed` with the new
+/// region and locale.
+pub async fn set_region<R: Runtime>(
+    app: &AppHandle<R>,
+    region: Option<&str>,
+) -> crate::Result<RegionLocale> {
+    let store = app.store(STORE_FILE)?;
+    match region {
+        Some(region) => {
+            lcu_region(region).ok_or_else(|| LocaleError::UnsupportedRegion(region.to_owned()))?;
+            store.set(REGION_KEY, region);
+        }
+        None => _ = store.delete(REGION_KEY),
+    }
+
+    let region = initial_region(app);
+    let state = app.state::<State>();
+    let changed = {
+        let mut current = state.region.write().await;
+        let changed = *current != region;
+        *current = region;
+        changed
+    };
+    if changed {
+        // Switch to the versions live in the region.
+        patch::update(app).await?;
+    }
+
+    let current = current(app).await;
+    app.emit("locale-changed", &current)?;
+    Ok(current)
+}
+
+/// Rebuild the u.gg client's data in `locale` and emit `locale-changed`.
+async fn apply<R: Runtime>(app: &AppHandle<R>, locale: &str) -> crate::Result<RegionLocale> {
+    let state = app.state::<State>();
+    let mut client = state.client().await;
+    if !client
+        .ddragon()
+        .get_languages()
+        .await?
+        .iter()
+        .any(|l| l == locale)
+    {
+        return Err(LocaleError::Unsupported(locale.to_owned()).into());
+    }
+
+    while client.ddragon().locale() != locale {
+        let ddragon = client.ddragon();
+        let new = patch::builder(locale, ddragon.realm(), Some(client.version()))
+            .build(app)
+            .await?;
+        let mut current = state.client.write().await;
+        // Swapped while building, e.g. for a new patch; rebuild from that
+        // client so its version isn't reverted.
+        if Arc::ptr_eq(&current, &client) {
+            *current = Arc::new(new);
+            break;
+        }
+        client = Arc::clone(&current);
+        drop(current);
+    }
+
+    let current = current(app).await;
+    app.emit("locale-changed", &current)?;
+    Ok(current)
+}
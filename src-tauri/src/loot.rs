@@ -0,0 +1,174 @@
+//! Hextech loot advisor. Suggests which champion shards to upgrade or
+//! disenchant, but never crafts anything unless the user confirms it with
+//! [`craft`].
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_lcu::{LcuExt, models::loot::LootItem};
+use tauri_plugin_store::StoreExt;
+
+use crate::{STORE_FILE, State, error::LootError};
+
+/// Store key of the user's champion pool, a list of champion IDs.
+const CHAMPION_POOL_KEY: &str = "champion_pool";
+/// Mastery level at which a champion counts as played even if it isn't in the
+/// champion pool.
+const PLAYED_MASTERY_LEVEL: i64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LootAction {
+    Upgrade,
+    Disenchant,
+    Keep,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AdviceReason {
+    /// Unowned champion in the user's champion pool.
+    ChampionPool,
+    /// Unowned champion with high mastery.
+    HighMastery,
+    /// Unowned champion the user doesn't play.
+    NotPlayed,
+    /// The user already owns the champion.
+    Owned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LootAdvice {
+    pub loot_id: String,
+    pub champion_id: i64,
+    pub name: String,
+    pub count: i64,
+    pub action: LootAction,
+    /// Number of shards the action applies to.
+    pub quantity: i64,
+    /// Blue essence gained (positive) or spent (negative) by the action.
+    pub blue_essence: i64,
+    pub reason: AdviceReason,
+}
+
+fn champion_pool<R: Runtime>(app: &AppHandle<R>) -> HashSet<i64> {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get(CHAMPION_POOL_KEY))
+        .and_then(|pool| serde_json::from_value(pool).ok())
+        .unwrap_or_default()
+}
+
+/// Suggest an action for every champion shard in the player's loot.
+pub async fn advise<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Vec<LootAdvice>> {
+    let loot = app.lcu().get_player_loot().await?;
+    let mastery = app
+        .lcu()
+        .get_champion_mastery()
+        .await?
+        .into_iter()
+        .map(|mastery| (mastery.champion_id, mastery.champion_level))
+        .collect::<HashMap<_, _>>();
+    let pool = champion_pool(app);
+    let state = app.state::<State>();
+    let client = state.client().await;
+
+    let mut advice = loot
+        .iter()
+        .filter(|item| item.is_champion() && item.is_rental && item.count > 0)
+        .map(|item| {
+            let champion_id = item.store_item_id;
+            let name = client
+                .champion_by_key(champion_id)
+                .map_or_else(|| item.item_desc.clone(), |champ| champ.name.clone());
+            let level = mastery.get(&champion_id).copied().unwrap_or_default();
+            let (action, quantity, reason) = advise_shard(item, pool.contains(&champion_id), level);
+            let blue_essence = match action {
+                LootAction::Upgrade => -item.upgrade_essence_value * quantity,
+                LootAction::Disenchant => item.disenchant_value * quantity,
+                LootAction::Keep => 0,
+            };
+
+            LootAdvice {
+                loot_id: item.loot_id.clone(),
+                champion_id,
+                name,
+                count: item.count,
+                action,
+                quantity,
+                blue_essence,
+                reason,
+            }
+        })
+        .collect::<Vec<_>>();
+    advice.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(advice)
+}
+
+/// Unlock unowned champions the user plays, disenchant shards of owned
+/// champions, and keep one shard of everything else for later.
+fn advise_shard(
+    item: &LootItem,
+    in_pool: bool,
+    mastery_level: i64,
+) -> (LootAction, i64, AdviceReason) {
+    if item.is_owned() {
+        (LootAction::Disenchant, item.count, AdviceReason::Owned)
+    } else if in_pool {
+        (LootAction::Upgrade, 1, AdviceReason::ChampionPool)
+    } else if mastery_level >= PLAYED_MASTERY_LEVEL {
+        (LootAction::Upgrade, 1, AdviceReason::HighMastery)
+    } else if item.count > 1 {
+        (
+            LootAction::Disenchant,
+            item.count - 1,
+            AdviceReason::NotPlayed,
+        )
+    } else {
+        (LootAction::Keep, 1, AdviceReason::NotPlayed)
+    }
+}
+
+/// Run `action` on `repeat` of the loot item `loot_id`. Must only be called
+/// after the user confirms the action.
+pub async fn craft<R: Runtime>(
+    app: &AppHandle<R>,
+    loot_id: &str,
+    action: LootAction,
+    repeat: u32,
+) -> crate::Result<()> {
+    let kind = match action {
+        LootAction::Upgrade => "UPGRADE",
+        LootAction::Disenchant => "DISENCHANT",
+        LootAction::Keep => return Ok(()),
+    };
+    let recipe = app
+        .lcu()
+        .get_loot_recipes(loot_id)
+        .await?
+        .into_iter()
+        .find(|recipe| recipe.kind == kind)
+        .ok_or_else(|| LootError::NoRecipe(loot_id.to_owned()))?;
+    // Use `loot_id` for its own slot and the first option for the others,
+    // e.g. blue essence for upgrades.
+    let loot_ids = recipe
+        .slots
+        .iter()
+        .filter_map(|slot| {
+            if slot.loot_ids.iter().any(|id| id == loot_id) {
+                Some(loot_id)
+            } else {
+                slot.loot_ids.first().map(String::as_str)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    app.lcu()
+        .craft_loot(&recipe.recipe_name, &loot_ids, repeat)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,254 @@
+//! Ranked LP history of the local player, recorded after each ranked game.
+//!
+//! History is saved in the store, keyed by puuid and then by queue type.
+
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, async_runtime};
+use tauri_plugin_lcu::{LcuExt, models::ranked::RankedQueueStats};
+use tauri_plugin_store::StoreExt;
+use tokio::time::{self, Duration, Instant};
+
+use crate::{STORE_FILE, State};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How long to wait for ranked stats to update after a game.
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Store key of the LP history.
+const LP_HISTORY_KEY: &str = "lp_history";
+/// Tiers with divisions, from lowest to highest. Apex tiers share the LP
+/// ladder above these.
+const TIERS: [&str; 7] = [
+    "IRON", "BRONZE", "SILVER", "GOLD", "PLATINUM", "EMERALD", "DIAMOND",
+];
+const DIVISIONS: [&str; 4] = ["IV", "III", "II", "I"];
+
+/// Ranked stats in one queue at a point in time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LpEntry {
+    /// Unix timestamp in milliseconds.
+    pub timestamp: i64,
+    /// The game that caused the change, or `None` if it happened while krugg
+    /// wasn't running.
+    pub game_id: Option<i64>,
+    pub tier: String,
+    pub division: String,
+    pub league_points: i64,
+    pub wins: i64,
+    pub losses: i64,
+    /// LP counted from Iron IV 0 LP, for charting across divisions.
+    pub total_lp: i64,
+}
+
+/// Win and loss streaks of games in the LP history.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LpStreaks {
+    /// Positive for a win streak, negative for a loss streak.
+    pub current: i64,
+    pub longest_win: i64,
+    pub longest_loss: i64,
+}
+
+/// puuid -> queue type -> entries, oldest first.
+type History = HashMap<String, HashMap<String, Vec<LpEntry>>>;
+
+/// A ranked game whose result isn't in the ranked stats yet.
+#[derive(Debug)]
+struct Pending {
+    game_id: i64,
+    queue_type: String,
+    since: Instant,
+    /// Recorded, or gave up waiting for the ranked stats to update.
+    done: bool,
+}
+
+/// LP counted from Iron IV 0 LP.
+fn total_lp(tier: &str, division: &str, league_points: i64) -> i64 {
+    let tier = tier.to_ascii_uppercase();
+    let (tier, division) = match TIERS.iter().position(|&t| t == tier) {
+        Some(tier) => (tier, DIVISIONS.iter().position(|&d| d == division)),
+        // Apex tiers and unranked.
+        None if tier.is_empty() || tier == "NONE" => return 0,
+        None => (TIERS.len(), Some(0)),
+    };
+
+    (tier * DIVISIONS.len() + division.unwrap_or_default()) as i64 * 100 + league_points
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+}
+
+fn load<R: Runtime>(app: &AppHandle<R>) -> History {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get(LP_HISTORY_KEY))
+        .and_then(|history| serde_json::from_value(history).ok())
+        .unwrap_or_default()
+}
+
+fn save<R: Runtime>(app: &AppHandle<R>, history: &History) -> crate::Result<()> {
+    app.store(STORE_FILE)?
+        .set(LP_HISTORY_KEY, serde_json::to_value(history)?);
+    Ok(())
+}
+
+/// Append the player's current stats in `stats.queue_type` if they changed
+/// since the last entry. Returns whether an entry was added.
+fn record<R: Runtime>(
+    app: &AppHandle<R>,
+    puuid: &str,
+    stats: &RankedQueueStats,
+    game_id: Option<i64>,
+) -> crate::Result<bool> {
+    let mut history = load(app);
+    let entries = history
+        .entry(puuid.to_owned())
+        .or_default()
+        .entry(stats.queue_type.clone())
+        .or_default();
+    if entries.last().is_some_and(|last| {
+        last.wins == stats.wins
+            && last.losses == stats.losses
+            && last.league_points == stats.league_points
+            && last.tier == stats.tier
+            && last.division == stats.division
+    }) {
+        return Ok(false);
+    }
+
+    entries.push(LpEntry {
+        timestamp: now(),
+        game_id,
+        tier: stats.tier.clone(),
+        division: stats.division.clone(),
+        league_points: stats.league_points,
+        wins: stats.wins,
+        losses: stats.losses,
+        total_lp: total_lp(&stats.tier, &stats.division, stats.league_points),
+    });
+    save(app, &history)?;
+    Ok(true)
+}
+
+/// LP history of the current summoner in `queue_type`, oldest first.
+pub async fn history<R: Runtime>(
+    app: &AppHandle<R>,
+    queue_type: &str,
+) -> crate::Result<Vec<LpEntry>> {
+    let puuid = app.lcu().get_current_summoner().await?.puuid;
+    Ok(load(app)
+        .remove(&puuid)
+        .and_then(|mut queues| queues.remove(queue_type))
+        .unwrap_or_default())
+}
+
+/// Win and loss streaks of the current summoner in `queue_type`.
+pub async fn streaks<R: Runtime>(app: &AppHandle<R>, queue_type: &str) -> crate::Result<LpStreaks> {
+    let entries = history(app, queue_type).await?;
+    let mut streaks = LpStreaks::default();
+
+    for pair in entries.windows(2) {
+        let [prev, entry] = pair else { continue };
+        let won = entry.wins > prev.wins;
+        let lost = entry.losses > prev.losses;
+        streaks.current = match (won, lost) {
+            (true, false) => streaks.current.max(0) + 1,
+            (false, true) => streaks.current.min(0) - 1,
+            // Several games or a reset between entries.
+            _ => 0,
+        };
+        streaks.longest_win = streaks.longest_win.max(streaks.current);
+        streaks.longest_loss = streaks.longest_loss.max(-streaks.current);
+    }
+
+    Ok(streaks)
+}
+
+/// Record the player's ranked stats when the client connects, and again after
+/// each ranked game once the result is in.
+pub fn watch<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<State>();
+    let cancel_token = state.cancel_token.clone();
+    let app = app.clone();
+
+    async_runtime::spawn(state.tracker.track_future(async move {
+        let mut interval = time::interval(POLL_INTERVAL);
+        let mut baseline = false;
+        let mut pending = None;
+
+        loop {
+            tokio::select! {
+                biased;
+                () = cancel_token.cancelled() => break,
+                _ = interval.tick() => {
+                    if !app.lcu().connected().await {
+                        baseline = false;
+                        continue;
+                    }
+                    _ = poll(&app, &mut baseline, &mut pending).await;
+                }
+            }
+        }
+    }));
+}
+
+async fn poll<R: Runtime>(
+    app: &AppHandle<R>,
+    baseline: &mut bool,
+    pending: &mut Option<Pending>,
+) -> crate::Result<()> {
+    let lcu = app.lcu();
+    if let Ok(session) = lcu.get_gameflow_session().await
+        && session.game_data.queue.is_ranked
+        && matches!(session.phase.as_str(), "WaitingForStats" | "EndOfGame")
+        && pending
+            .as_ref()
+            .is_none_or(|p| p.game_id != session.game_data.game_id)
+    {
+        *pending = Some(Pending {
+            game_id: session.game_data.game_id,
+            queue_type: session.game_data.queue.kind,
+            since: Instant::now(),
+            done: false,
+        });
+    }
+    if *baseline && pending.as_ref().is_none_or(|game| game.done) {
+        return Ok(());
+    }
+
+    let puuid = lcu.get_current_summoner().await?.puuid;
+    let stats = lcu.get_current_ranked_stats().await?;
+    if !*baseline {
+        // Catch up on changes while krugg wasn't running, so the next game
+        // is compared to the current stats. These entries have no game.
+        for queue in stats.queues.iter().filter(|queue| queue.is_ranked()) {
+            record(app, &puuid, queue, None)?;
+        }
+        *baseline = true;
+    }
+
+    if let Some(game) = pending.as_mut()
+        && !game.done
+    {
+        let recorded = match stats
+            .queues
+            .iter()
+            .find(|queue| queue.queue_type == game.queue_type)
+        {
+            Some(queue) => record(app, &puuid, queue, Some(game.game_id))?,
+            None => false,
+        };
+        game.done = recorded || game.since.elapsed() > UPDATE_TIMEOUT;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Patch rollover: rebuild the u.gg client's static data when a new patch
+//! has u.gg stats or goes live in the user's region.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime, async_runtime};
+use tokio::time::{self, Duration, MissedTickBehavior};
+
+use crate::{State, locale, ugg};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchChanged {
+    /// ddragon version like `15.7.1`.
+    pub old: String,
+    pub new: String,
+}
+
+/// Check for a new patch every [`POLL_INTERVAL`], swap in a client built for
+/// it, and emit `patch-changed`.
+pub fn watch<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<State>();
+    let cancel_token = state.cancel_token.clone();
+    let app = app.clone();
+
+    async_runtime::spawn(state.tracker.track_future(async move {
+        let mut interval = time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The client was just built in setup.
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                biased;
+                () = cancel_token.cancelled() => break,
+                _ = interval.tick() => {
+                    _ = update(&app).await;
+                }
+            }
+        }
+    }));
+}
+
+/// Builder of a u.gg client with the given settings.
+pub const fn builder<'a>(
+    locale: &'a str,
+    realm: Option<&'a str>,
+    version: Option<&'a str>,
+) -> ugg::ClientBuilder<'a> {
+    let mut builder = ugg::ClientBuilder::new().locale(locale);
+    if let Some(realm) = realm {
+        builder = builder.realm(realm);
+    }
+    if let Some(version) = version {
+        builder = builder.version(version);
+    }
+    builder
+}
+
+/// Swap in a new client if there's a new patch or the region's realm
+/// changed, and emit `patch-changed` if the version changed.
+pub async fn update<R: Runtime>(app: &AppHandle<R>) -> crate::Result<()> {
+    let state = app.state::<State>();
+    let client = state.client().await;
+    let ddragon = client.ddragon();
+    let locale = ddragon.locale();
+    let realm = locale::ddragon_realm(*state.region.read().await);
+
+    // Built from the bundled dragontail while offline, so try data dragon
+    // again even if there's no new patch.
+    let new = if ddragon.is_local() {
+        let new = builder(locale, realm, None).build(app).await?;
+        if new.ddragon().is_local() {
+            return Ok(());
+        }
+        new
+    } else if realm != ddragon.realm() {
+        builder(locale, realm, None).build(app).await?
+    } else {
+        match client.latest_supported_version().await? {
+            Some(version) if version != client.version() => {
+                builder(locale, realm, Some(&version)).build(app).await?
+            }
+            _ => return Ok(()),
+        }
+    };
+
+    let payload = PatchChanged {
+        old: client.version().to_owned(),
+        new: new.version().to_owned(),
+    };
+    {
+        let mut current = state.client.write().await;
+        // Rebuilt in the meantime, e.g. for another locale; check again next
+        // time.
+        if !Arc::ptr_eq(&current, &client) {
+            return Ok(());
+        }
+        *current = Arc::new(new);
+    }
+
+    if payload.old != payload.new {
+        app.emit("patch-changed", &payload)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! Player lookup by Riot ID.
+
+use std::cmp::Reverse;
+
+use ddragon::models::profile_icons::ProfileIcon;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_lcu::{
+    LcuExt,
+    models::{mastery::ChampionMastery, ranked::RankedQueueStats, summoner::Summoner},
+};
+
+use crate::State;
+
+/// Number of top mastery champions in a profile.
+const TOP_MASTERY: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileBundle {
+    pub summoner: Summoner,
+    pub profile_icon: Option<ProfileIcon>,
+    /// Ranked stats for every queue the player is ranked in.
+    pub ranked: Vec<RankedQueueStats>,
+    /// Highest mastery champions, sorted by points descending.
+    pub top_mastery: Vec<ChampionMastery>,
+}
+
+/// Look up a player by their Riot ID, formatted as `name#tag`.
+pub async fn lookup<R: Runtime>(app: &AppHandle<R>, riot_id: &str) -> crate::Result<ProfileBundle> {
+    let lcu = app.lcu();
+    let summoner = lcu.get_summoner_by_riot_id(riot_id).await?;
+
+    let state = app.state::<State>();
+    let client = state.client().await;
+    let profile_icon = client
+        .ddragon()
+        .get_profile_icons()
+        .await?
+        .remove(&summoner.profile_icon_id.to_string());
+    let ranked = lcu
+        .get_ranked_stats(&summoner.puuid)
+        .await?
+        .queues
+        .into_iter()
+        .filter(RankedQueueStats::is_ranked)
+        .collect();
+    let mut top_mastery = lcu.get_champion_mastery_of(summoner.summoner_id).await?;
+    top_mastery.sort_unstable_by_key(|mastery| Reverse(mastery.champion_points));
+    top_mastery.truncate(TOP_MASTERY);
+
+    Ok(ProfileBundle {
+        summoner,
+        profile_icon,
+        ranked,
+        top_mastery,
+    })
+}
@@ -0,0 +1,164 @@
+//! Named backups of in-game settings and keybinds, stored as JSON files in the
+//! app data dir.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_lcu::LcuExt;
+use tokio::fs;
+
+use crate::error::ProfileError;
+
+const PROFILES_DIR: &str = "settings_profiles";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsProfile {
+    pub game_settings: Value,
+    pub input_settings: Value,
+}
+
+/// A setting that differs between two profiles. `path` is the dot separated
+/// path to the setting, and `left`/`right` are `None` if it's missing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingDiff {
+    pub path: String,
+    pub left: Option<Value>,
+    pub right: Option<Value>,
+}
+
+fn profiles_dir<R: Runtime>(app: &AppHandle<R>) -> crate::Result<PathBuf> {
+    Ok(app.path().app_data_dir()?.join(PROFILES_DIR))
+}
+
+/// Path to the profile file. Names are restricted so a profile can't be
+/// written outside of the profiles dir.
+fn profile_path<R: Runtime>(app: &AppHandle<R>, name: &str) -> crate::Result<PathBuf> {
+    let valid = !name.trim().is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'));
+    if !valid {
+        return Err(ProfileError::InvalidName(name.to_owned()).into());
+    }
+
+    Ok(profiles_dir(app)?.join(format!("{name}.json")))
+}
+
+/// Save the client's current settings to the profile `name`, replacing it if
+/// it exists.
+pub async fn export<R: Runtime>(app: &AppHandle<R>, name: &str) -> crate::Result<()> {
+    let path = profile_path(app, name)?;
+    let profile = SettingsProfile {
+        game_settings: app.lcu().get_game_settings().await?,
+        input_settings: app.lcu().get_input_settings().await?,
+    };
+
+    fs::create_dir_all(profiles_dir(app)?).await?;
+    fs::write(path, serde_json::to_vec_pretty(&profile)?).await?;
+
+    Ok(())
+}
+
+/// Load the profile `name`.
+pub async fn load<R: Runtime>(app: &AppHandle<R>, name: &str) -> crate::Result<SettingsProfile> {
+    let path = profile_path(app, name)?;
+    if !is_file(&path).await {
+        return Err(ProfileError::NotFound(name.to_owned()).into());
+    }
+
+    Ok(serde_json::from_slice(&fs::read(path).await?)?)
+}
+
+async fn is_file(path: &Path) -> bool {
+    fs::metadata(path)
+        .await
+        .is_ok_and(|metadata| metadata.is_file())
+}
+
+/// Names of all saved profiles, sorted.
+pub async fn list<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Vec<String>> {
+    let mut entries = match fs::read_dir(profiles_dir(app)?).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut names = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+            && let Some(name) = path.file_stem().and_then(|name| name.to_str())
+        {
+            names.push(name.to_owned());
+        }
+    }
+    names.sort_unstable();
+
+    Ok(names)
+}
+
+/// Apply the profile `name` to the client.
+pub async fn apply<R: Runtime>(app: &AppHandle<R>, name: &str) -> crate::Result<()> {
+    let profile = load(app, name).await?;
+    app.lcu()
+        .patch_game_settings(&profile.game_settings)
+        .await?;
+    app.lcu()
+        .patch_input_settings(&profile.input_settings)
+        .await?;
+
+    Ok(())
+}
+
+/// All settings that differ between the profiles `left` and `right`.
+pub async fn diff<R: Runtime>(
+    app: &AppHandle<R>,
+    left: &str,
+    right: &str,
+) -> crate::Result<Vec<SettingDiff>> {
+    let left = serde_json::to_value(load(app, left).await?)?;
+    let right = serde_json::to_value(load(app, right).await?)?;
+    let mut diffs = Vec::new();
+    diff_values(String::new(), Some(&left), Some(&right), &mut diffs);
+
+    Ok(diffs)
+}
+
+fn diff_values(
+    path: String,
+    left: Option<&Value>,
+    right: Option<&Value>,
+    diffs: &mut Vec<SettingDiff>,
+) {
+    match (left, right) {
+        (Some(Value::Object(l)), Some(Value::Object(r))) => {
+            let mut keys = l.keys().chain(r.keys()).collect::<Vec<_>>();
+            keys.sort_unstable();
+            keys.dedup();
+
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_values(path, l.get(key), r.get(key), diffs);
+            }
+        }
+        (l, r) if l != r => diffs.push(SettingDiff {
+            path,
+            left: l.cloned(),
+            right: r.cloned(),
+        }),
+        _ => (),
+    }
+}
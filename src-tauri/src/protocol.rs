@@ -0,0 +1,207 @@
+//! `ddragon://` URI scheme serving data dragon images, so they work offline
+//! and from the CSP's own origins.
+//!
+//! Paths are `{kind}/{id}`, e.g. `champion/Ahri`, `item/3089`, `perk/8112`,
+//! `spell/SummonerFlash`, `profileicon/29`, `splash/Ahri/1`, or
+//! `atlas/champion` for an [atlas](crate::atlas), or `cdragon/{path}` for a
+//! [CommunityDragon](crate::cdragon) asset path like
+//! `cdragon/lol-game-data/assets/v1/perk-images/...`. The optional query
+//! resizes (`size`) and converts (`format=png|webp`) the image.
+
+use std::{io::Cursor, num::NonZeroUsize, sync::Arc};
+
+use image::{DynamicImage, ImageFormat, imageops::FilterType};
+use lru::LruCache;
+use percent_encoding::percent_decode_str;
+use tauri::{
+    AppHandle, Manager, Runtime, UriSchemeContext, UriSchemeResponder,
+    async_runtime::{self, Mutex},
+    http::{Request, Response, StatusCode, header},
+};
+use url::form_urlencoded;
+
+use crate::{Error, State, atlas, error::ProtocolError};
+
+pub const SCHEME: &str = "ddragon";
+/// Max width and height of resized images.
+const MAX_SIZE: u32 = 1024;
+
+/// Encoded images by version and request path and query.
+#[derive(Debug)]
+pub struct ImageCache(Mutex<LruCache<String, Arc<[u8]>>>);
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self(Mutex::new(LruCache::new(NonZeroUsize::new(256).unwrap())))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Options {
+    /// Max width and height.
+    size: Option<u32>,
+    format: ImageFormat,
+}
+
+impl Options {
+    fn parse(query: &str) -> crate::Result<Self> {
+        let mut options = Self {
+            size: None,
+            format: ImageFormat::Png,
+        };
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            let invalid = || ProtocolError::InvalidQuery(format!("{key}={value}"));
+            match &*key {
+                "size" => {
+                    let size = value.parse().map_err(|_| invalid())?;
+                    if !(1..=MAX_SIZE).contains(&size) {
+                        return Err(invalid().into());
+                    }
+                    options.size = Some(size);
+                }
+                "format" => {
+                    options.format = match &*value {
+                        "png" => ImageFormat::Png,
+                        "webp" => ImageFormat::WebP,
+                        _ => return Err(invalid().into()),
+                    };
+                }
+                _ => return Err(invalid().into()),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Get the image at `path`, see the [module docs](self).
+async fn image<R: Runtime>(app: &AppHandle<R>, path: &str) -> crate::Result<DynamicImage> {
+    let client = app.state::<State>().client().await;
+    let ddragon = client.ddragon();
+    let not_found = || ProtocolError::NotFound(path.to_owned());
+    let mut segments = path.split('/');
+    let (Some(kind), Some(id)) = (segments.next(), segments.next()) else {
+        return Err(not_found().into());
+    };
+
+    match kind {
+        "champion" => {
+            let champ = client.champion(id).ok_or_else(not_found)?;
+            ddragon.get_image_of(champ).await
+        }
+        "cdragon" => client.cdragon().get_image(&path[kind.len()..]).await,
+        "atlas" => {
+            let kind = atlas::AtlasKind::parse(id).ok_or_else(not_found)?;
+            Ok(atlas::atlas(app, kind).await?.image.clone())
+        }
+        "item" => {
+            let item = client.item(id).ok_or_else(not_found)?;
+            ddragon.get_image_of(item).await
+        }
+        "perk" => {
+            let id = id.parse().map_err(|_| not_found())?;
+            let icon = ddragon.get_rune_icon(id).await?.ok_or_else(not_found)?;
+            ddragon.get_cdn_image(&icon).await
+        }
+        "spell" => {
+            let spell = ddragon
+                .get_summoner_spell(id)
+                .await?
+                .ok_or_else(not_found)?;
+            ddragon.get_image_of(&spell).await
+        }
+        "profileicon" => {
+            let icon = ddragon
+                .get_profile_icons()
+                .await?
+                .remove(id)
+                .ok_or_else(not_found)?;
+            ddragon.get_image_of(&icon).await
+        }
+        "splash" => {
+            let champ = client.champion(id).ok_or_else(not_found)?;
+            let skin = segments.next().unwrap_or("0");
+            ddragon
+                .get_cdn_image(&format!("champion/splash/{}_{skin}.jpg", champ.id))
+                .await
+        }
+        _ => Err(not_found().into()),
+    }
+}
+
+/// Resize and encode `image`.
+fn encode(image: DynamicImage, options: Options) -> crate::Result<Vec<u8>> {
+    let image = match options.size {
+        Some(size) => image.resize(size, size, FilterType::Lanczos3),
+        None => image,
+    };
+    let image = match options.format {
+        // The WebP encoder only takes 8-bit RGB(A).
+        ImageFormat::WebP => DynamicImage::ImageRgba8(image.into_rgba8()),
+        _ => image,
+    };
+    let mut bytes = Cursor::new(Vec::new());
+    image.write_to(&mut bytes, options.format)?;
+
+    Ok(bytes.into_inner())
+}
+
+async fn respond<R: Runtime>(
+    app: &AppHandle<R>,
+    path: &str,
+    query: &str,
+) -> crate::Result<(Arc<[u8]>, ImageFormat)> {
+    let options = Options::parse(query)?;
+    let version = app.state::<State>().client().await.version().to_owned();
+    let key = format!("{version}/{path}?{query}");
+    let cache = app.state::<ImageCache>();
+    if let Some(bytes) = cache.0.lock().await.get(&key) {
+        return Ok((Arc::clone(bytes), options.format));
+    }
+
+    let image = image(app, path).await?;
+    let bytes =
+        Arc::<[u8]>::from(async_runtime::spawn_blocking(move || encode(image, options)).await??);
+    cache.0.lock().await.put(key, Arc::clone(&bytes));
+
+    Ok((bytes, options.format))
+}
+
+/// Respond with the image at the request path, e.g.
+/// `ddragon://localhost/champion/Ahri?size=64`. The path may be
+/// percent-encoded, as with `convertFileSrc`.
+pub fn handle<R: Runtime>(
+    ctx: UriSchemeContext<'_, R>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let app = ctx.app_handle().clone();
+    let path = percent_decode_str(request.uri().path())
+        .decode_utf8_lossy()
+        .trim_matches('/')
+        .to_owned();
+    let query = request.uri().query().unwrap_or_default().to_owned();
+
+    async_runtime::spawn(async move {
+        let response = match respond(&app, &path, &query).await {
+            Ok((bytes, format)) => Response::builder()
+                .header(header::CONTENT_TYPE, format.to_mime_type())
+                .body(bytes.to_vec()),
+            Err(err) => {
+                let status = match &err {
+                    Error::Protocol(ProtocolError::NotFound(_)) => StatusCode::NOT_FOUND,
+                    Error::Protocol(ProtocolError::InvalidQuery(_)) => StatusCode::BAD_REQUEST,
+                    _ => StatusCode::BAD_GATEWAY,
+                };
+                Response::builder()
+                    .status(status)
+                    .header(header::CONTENT_TYPE, "text/plain")
+                    .body(err.to_string().into_bytes())
+            }
+        };
+
+        if let Ok(response) = response {
+            responder.respond(response);
+        }
+    });
+}
@@ -0,0 +1,75 @@
+//! u.gg game modes of LCU queues.
+
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_lcu::{LcuExt, models::gameflow::GameflowSession};
+use ugg_types::mappings::{Mode, Role};
+
+const SUMMONERS_RIFT: i64 = 11;
+const HOWLING_ABYSS: i64 = 12;
+const NEXUS_BLITZ_MAP: i64 = 21;
+const RINGS_OF_WRATH: i64 = 30;
+
+/// u.gg game mode of a queue. Known queue IDs are mapped directly, then the
+/// map and game mode (e.g. `CLASSIC`, `ARAM`, `CHERRY`) are used for rotating
+/// and new queues.
+///
+/// See [queues.json](https://static.developer.riotgames.com/docs/lol/queues.json).
+pub fn mode(queue_id: i64, map_id: i64, game_mode: &str) -> Option<Mode> {
+    let mode = match queue_id {
+        // Normal draft, ranked solo, blind, ranked flex, quickplay, swiftplay
+        400 | 420 | 430 | 440 | 480 | 490 => Mode::Normal,
+        450 => Mode::ARAM,
+        // ARURF, snow ARURF
+        900 | 1010 => Mode::ARURF,
+        1020 => Mode::OneForAll,
+        1300 => Mode::NexusBlitz,
+        1700 | 1710 => Mode::Arena,
+        1900 => Mode::URF,
+        _ => match (map_id, game_mode.to_ascii_uppercase().as_str()) {
+            (_, "ARAM") | (HOWLING_ABYSS, _) => Mode::ARAM,
+            (_, "URF") => Mode::URF,
+            (_, "ONEFORALL") => Mode::OneForAll,
+            (_, "NEXUSBLITZ") | (NEXUS_BLITZ_MAP, _) => Mode::NexusBlitz,
+            (_, "CHERRY") | (RINGS_OF_WRATH, _) => Mode::Arena,
+            (_, "CLASSIC") | (SUMMONERS_RIFT, _) => Mode::Normal,
+            _ => return None,
+        },
+    };
+
+    Some(mode)
+}
+
+/// u.gg game mode of the queue in the gameflow `session`, if there is one.
+pub fn session_mode(session: &GameflowSession) -> Option<Mode> {
+    if session.phase.is_empty() || session.phase == "None" {
+        return None;
+    }
+    let queue = &session.game_data.queue;
+    let map_id = if queue.map_id == 0 {
+        session.map.id
+    } else {
+        queue.map_id
+    };
+    let game_mode = if queue.game_mode.is_empty() {
+        &session.map.game_mode
+    } else {
+        &queue.game_mode
+    };
+
+    mode(queue.id, map_id, game_mode)
+}
+
+/// u.gg game mode of what the local player is queued for or playing, if
+/// anything.
+pub async fn current_mode<R: Runtime>(app: &AppHandle<R>) -> Option<Mode> {
+    let session = app.lcu().get_gameflow_session().await.ok()?;
+    session_mode(&session)
+}
+
+/// The role to request u.gg stats for in `mode` if none is chosen.
+pub const fn default_role(mode: Mode) -> Role {
+    match mode {
+        Mode::ARAM => Role::None,
+        _ => Role::Automatic,
+    }
+}
@@ -0,0 +1,87 @@
+//! Personalized u.gg rank brackets from the local player's ranked tier.
+
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_lcu::LcuExt;
+use tauri_plugin_store::{JsonValue, StoreExt};
+use ugg_types::mappings::{Mode, Rank};
+
+use crate::STORE_FILE;
+
+/// Store key of the personalized rank setting.
+const PERSONALIZED_RANK_KEY: &str = "personalized_rank";
+pub const SOLO_QUEUE: &str = "RANKED_SOLO_5x5";
+const FLEX_QUEUE: &str = "RANKED_FLEX_SR";
+
+/// Plus brackets from narrowest to broadest, then all ranks.
+const PLUS_BRACKETS: [Rank; 6] = [
+    Rank::MasterPlus,
+    Rank::Diamond2Plus,
+    Rank::DiamondPlus,
+    Rank::EmeraldPlus,
+    Rank::PlatinumPlus,
+    Rank::Overall,
+];
+
+/// Ranks to request u.gg stats with, in order of preference.
+#[derive(Debug, Clone)]
+pub struct PreferredRanks {
+    pub ranks: Vec<Rank>,
+    /// Whether the ranks are brackets of the player's tier. Only then are
+    /// brackets with too few matches skipped for broader ones.
+    pub personalized: bool,
+}
+
+/// u.gg rank brackets matching a ranked `tier` like `EMERALD`, from the
+/// closest bracket to the broadest. Empty if `tier` is unknown.
+pub fn brackets(tier: &str) -> Vec<Rank> {
+    match tier.to_ascii_uppercase().as_str() {
+        "IRON" => vec![Rank::Iron, Rank::Overall],
+        "BRONZE" => vec![Rank::Bronze, Rank::Overall],
+        "SILVER" => vec![Rank::Silver, Rank::Overall],
+        "GOLD" => vec![Rank::Gold, Rank::Overall],
+        "PLATINUM" => PLUS_BRACKETS[4..].to_vec(),
+        "EMERALD" => PLUS_BRACKETS[3..].to_vec(),
+        "DIAMOND" => PLUS_BRACKETS[2..].to_vec(),
+        "MASTER" | "GRANDMASTER" | "CHALLENGER" => PLUS_BRACKETS.to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.store(STORE_FILE).is_ok_and(|store| {
+        matches!(
+            store.get(PERSONALIZED_RANK_KEY),
+            Some(JsonValue::Bool(true))
+        )
+    })
+}
+
+/// Ranks to request u.gg stats for `mode` with, in order of preference. Uses
+/// the local player's tier in the matching ranked queue if the personalized
+/// rank setting is enabled, otherwise u.gg's default order.
+pub async fn preferred_ranks<R: Runtime>(app: &AppHandle<R>, mode: Mode) -> PreferredRanks {
+    let default = || PreferredRanks {
+        ranks: Rank::preferred_order().to_vec(),
+        personalized: false,
+    };
+    if mode != Mode::Normal || !is_enabled(app) {
+        return default();
+    }
+    let Ok(stats) = app.lcu().get_current_ranked_stats().await else {
+        return default();
+    };
+
+    let brackets = stats
+        .queue(SOLO_QUEUE)
+        .or_else(|| stats.queue(FLEX_QUEUE))
+        .map(|queue| brackets(&queue.tier))
+        .unwrap_or_default();
+    if brackets.is_empty() {
+        default()
+    } else {
+        PreferredRanks {
+            ranks: brackets,
+            personalized: true,
+        }
+    }
+}
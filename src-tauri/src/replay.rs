@@ -0,0 +1,130 @@
+//! Replay review: key moments of a game from its saved timeline, and control
+//! of the open replay.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_lcu::{
+    LcuExt,
+    models::{
+        match_history::{MatchHistoryGame, TimelineEvent},
+        replay::{Playback, PlaybackUpdate, Render, RenderUpdate},
+    },
+};
+
+use crate::{State, ugg};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MomentKind {
+    /// A champion died.
+    Kill,
+    /// Dragon, Baron, Rift Herald, etc.
+    Monster,
+    /// Tower or inhibitor.
+    Building,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MomentPlayer {
+    /// Name to lock the camera onto, see [`lock_camera`].
+    pub name: String,
+    pub champion_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Moment {
+    /// Seconds since the game started.
+    pub time: f64,
+    pub kind: MomentKind,
+    pub killer: Option<MomentPlayer>,
+    pub victim: Option<MomentPlayer>,
+    /// Monster or building type, e.g. `BARON_NASHOR` or `TOWER_BUILDING`.
+    pub target: Option<String>,
+}
+
+fn player(
+    client: &ugg::Client,
+    game: &MatchHistoryGame,
+    participant_id: i64,
+) -> Option<MomentPlayer> {
+    let player = game.player(participant_id)?;
+    let champion_name = game
+        .participants
+        .iter()
+        .find(|p| p.participant_id == participant_id)
+        .and_then(|p| client.champion_by_key(p.champion_id))
+        .map(|champ| champ.name.clone());
+    let name = if player.game_name.is_empty() {
+        player.summoner_name.clone()
+    } else {
+        player.game_name.clone()
+    };
+
+    Some(MomentPlayer {
+        name,
+        champion_name,
+    })
+}
+
+fn moment(client: &ugg::Client, game: &MatchHistoryGame, event: &TimelineEvent) -> Option<Moment> {
+    let (kind, target) = match event.kind.as_str() {
+        "CHAMPION_KILL" => (MomentKind::Kill, None),
+        "ELITE_MONSTER_KILL" => {
+            let monster = if event.monster_sub_type.is_empty() {
+                &event.monster_type
+            } else {
+                &event.monster_sub_type
+            };
+            (MomentKind::Monster, Some(monster.clone()))
+        }
+        "BUILDING_KILL" => (MomentKind::Building, Some(event.building_type.clone())),
+        _ => return None,
+    };
+
+    Some(Moment {
+        time: event.timestamp as f64 / 1000.0,
+        kind,
+        killer: player(client, game, event.killer_id),
+        victim: player(client, game, event.victim_id),
+        target,
+    })
+}
+
+/// Kills, objectives, and buildings of the game with `game_id`, in order.
+pub async fn moments<R: Runtime>(app: &AppHandle<R>, game_id: i64) -> crate::Result<Vec<Moment>> {
+    let lcu = app.lcu();
+    let game = lcu.get_game(game_id).await?;
+    let timeline = lcu.get_game_timeline(game_id).await?;
+    let client = app.state::<State>().client().await;
+
+    Ok(timeline
+        .frames
+        .iter()
+        .flat_map(|frame| &frame.events)
+        .filter_map(|event| moment(&client, &game, event))
+        .collect())
+}
+
+/// Seek, pause, or change the speed of the open replay.
+pub async fn update_playback<R: Runtime>(
+    app: &AppHandle<R>,
+    update: PlaybackUpdate,
+) -> crate::Result<Playback> {
+    Ok(app.lcu().replay()?.update_playback(&update).await?)
+}
+
+/// Lock the camera of the open replay onto the player `name`, or unlock it if
+/// `None`.
+pub async fn lock_camera<R: Runtime>(
+    app: &AppHandle<R>,
+    name: Option<String>,
+) -> crate::Result<Render> {
+    let update = RenderUpdate {
+        camera_attached: Some(name.is_some()),
+        selection_name: name,
+        ..RenderUpdate::default()
+    };
+    Ok(app.lcu().replay()?.update_render(&update).await?)
+}
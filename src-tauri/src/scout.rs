@@ -0,0 +1,292 @@
+//! Per-player summaries of the players in champ select, and of the enemy team
+//! once the game is loading.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime, async_runtime};
+use tauri_plugin_lcu::{
+    LcuExt,
+    models::{gameflow::GameflowPlayer, match_history::MatchHistoryGame, ranked::RankedQueueStats},
+};
+
+use crate::{State, error::ScoutError};
+
+const SOLO_QUEUE: &str = "RANKED_SOLO_5x5";
+/// Number of recent games to look at.
+const RECENT_GAMES: u32 = 20;
+/// Minimum number of recent games before giving autofill or OTP hints.
+const MIN_HINT_GAMES: usize = 5;
+/// Share of recent games on one champion to count as a one-trick.
+const OTP_SHARE: f64 = 0.7;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSummary {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub ally: bool,
+    /// Lowercase position, e.g. `top` or `utility`, or empty if unknown.
+    pub position: String,
+    pub champion_id: i64,
+    pub champion_name: Option<String>,
+    /// Solo queue rank, if ranked.
+    pub rank: Option<RankSummary>,
+    /// `None` if the player's summoner ID is hidden, like other players' in
+    /// champ select.
+    pub champion_mastery_level: Option<i64>,
+    pub champion_mastery_points: Option<i64>,
+    /// Recent games played on the picked champion.
+    pub champion_games: usize,
+    pub recent_games: usize,
+    pub recent_win_rate: Option<f64>,
+    /// The player is playing a position they rarely play.
+    pub autofill: bool,
+    /// Champion the player almost exclusively plays.
+    pub one_trick: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankSummary {
+    pub tier: String,
+    pub division: String,
+    pub league_points: i64,
+    pub wins: i64,
+    pub losses: i64,
+}
+
+/// A player to scout, from either champ select or the gameflow session.
+struct Player {
+    puuid: String,
+    summoner_id: i64,
+    game_name: String,
+    tag_line: String,
+    ally: bool,
+    position: String,
+    champion_id: i64,
+}
+
+impl From<&RankedQueueStats> for RankSummary {
+    fn from(stats: &RankedQueueStats) -> Self {
+        Self {
+            tier: stats.tier.clone(),
+            division: stats.division.clone(),
+            league_points: stats.league_points,
+            wins: stats.wins,
+            losses: stats.losses,
+        }
+    }
+}
+
+impl Player {
+    fn from_gameflow(player: &GameflowPlayer, ally: bool) -> Self {
+        Self {
+            puuid: player.puuid.clone(),
+            summoner_id: player.summoner_id,
+            game_name: player.game_name.clone(),
+            tag_line: player.tag_line.clone(),
+            ally,
+            position: match player.selected_position.as_str() {
+                "NONE" => String::new(),
+                position => position.to_lowercase(),
+            },
+            champion_id: player.champion_id,
+        }
+    }
+}
+
+/// Players of the current game. Both teams are available once the game is
+/// loading, and only allies during champ select.
+async fn players<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Vec<Player>> {
+    let lcu = app.lcu();
+
+    if let Ok(session) = lcu.get_gameflow_session().await
+        && !session.game_data.team_one.is_empty()
+    {
+        let puuid = lcu.get_current_summoner().await?.puuid;
+        let (allies, enemies) = if session
+            .game_data
+            .team_one
+            .iter()
+            .any(|player| player.puuid == puuid)
+        {
+            (&session.game_data.team_one, &session.game_data.team_two)
+        } else {
+            (&session.game_data.team_two, &session.game_data.team_one)
+        };
+
+        return Ok(allies
+            .iter()
+            .map(|player| Player::from_gameflow(player, true))
+            .chain(
+                enemies
+                    .iter()
+                    .map(|player| Player::from_gameflow(player, false)),
+            )
+            .collect());
+    }
+
+    let session = lcu.get_champ_select_session().await?;
+    let players = session
+        .my_team
+        .into_iter()
+        .filter(|player| !player.puuid.is_empty())
+        .map(|player| Player {
+            puuid: player.puuid,
+            summoner_id: player.summoner_id,
+            game_name: player.game_name,
+            tag_line: player.tag_line,
+            ally: true,
+            position: player.assigned_position,
+            champion_id: if player.champion_id == 0 {
+                player.champion_pick_intent
+            } else {
+                player.champion_id
+            },
+        })
+        .collect::<Vec<_>>();
+
+    if players.is_empty() {
+        return Err(ScoutError::NoPlayers.into());
+    }
+
+    Ok(players)
+}
+
+/// Summarize every player in the current champ select or game, all at once.
+pub async fn scout<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Vec<PlayerSummary>> {
+    let tasks = players(app)
+        .await?
+        .into_iter()
+        .map(|player| {
+            let app = app.clone();
+            async_runtime::spawn(async move { summarize(&app, player).await })
+        })
+        .collect::<Vec<_>>();
+    let mut summaries = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        summaries.push(task.await?);
+    }
+
+    Ok(summaries)
+}
+
+/// Summarize a player. Missing data (e.g. private match history) is left
+/// empty instead of failing the whole summary.
+async fn summarize<R: Runtime>(app: &AppHandle<R>, player: Player) -> PlayerSummary {
+    let lcu = app.lcu();
+    let state = app.state::<State>();
+    let client = state.client().await;
+
+    let (stats, mastery, history) = tokio::join!(
+        lcu.get_ranked_stats(&player.puuid),
+        async {
+            // Hidden as 0.
+            if player.summoner_id == 0 {
+                return None;
+            }
+            lcu.get_champion_mastery_of(player.summoner_id).await.ok()
+        },
+        lcu.get_match_history(&player.puuid, RECENT_GAMES),
+    );
+    let rank = stats
+        .ok()
+        .and_then(|stats| stats.queue(SOLO_QUEUE).map(RankSummary::from));
+    let mastery = mastery.map(|mastery| {
+        mastery
+            .into_iter()
+            .find(|mastery| mastery.champion_id == player.champion_id)
+            .unwrap_or_default()
+    });
+    let games = history
+        .map(|history| history.games.games)
+        .unwrap_or_default();
+
+    let wins = games
+        .iter()
+        .filter(|game| game.participant().is_some_and(|p| p.stats.win))
+        .count();
+    let champion_games = games
+        .iter()
+        .filter(|game| {
+            game.participant()
+                .is_some_and(|p| p.champion_id == player.champion_id)
+        })
+        .count();
+
+    PlayerSummary {
+        champion_name: client
+            .champion_by_key(player.champion_id)
+            .map(|champ| champ.name.clone()),
+        rank,
+        champion_mastery_level: mastery.as_ref().map(|mastery| mastery.champion_level),
+        champion_mastery_points: mastery.as_ref().map(|mastery| mastery.champion_points),
+        champion_games,
+        recent_games: games.len(),
+        recent_win_rate: (!games.is_empty()).then(|| wins as f64 / games.len() as f64),
+        autofill: is_autofill(&games, &player.position),
+        one_trick: one_trick(&games),
+        puuid: player.puuid,
+        game_name: player.game_name,
+        tag_line: player.tag_line,
+        ally: player.ally,
+        position: player.position,
+        champion_id: player.champion_id,
+    }
+}
+
+/// Position of a match history game, in the same format as champ select's
+/// assigned position.
+fn game_position(game: &MatchHistoryGame) -> Option<&'static str> {
+    let timeline = &game.participant()?.timeline;
+    match (timeline.lane.as_str(), timeline.role.as_str()) {
+        ("TOP", _) => Some("top"),
+        ("JUNGLE", _) => Some("jungle"),
+        ("MIDDLE" | "MID", _) => Some("middle"),
+        ("BOTTOM" | "BOT", "DUO_SUPPORT") => Some("utility"),
+        ("BOTTOM" | "BOT", _) => Some("bottom"),
+        _ => None,
+    }
+}
+
+/// Whether `position` is not the player's most played recent position.
+fn is_autofill(games: &[MatchHistoryGame], position: &str) -> bool {
+    if position.is_empty() {
+        return false;
+    }
+
+    let mut counts = HashMap::new();
+    for position in games.iter().filter_map(game_position) {
+        *counts.entry(position).or_insert(0_usize) += 1;
+    }
+    if counts.values().sum::<usize>() < MIN_HINT_GAMES {
+        return false;
+    }
+
+    counts
+        .into_iter()
+        .max_by_key(|&(_, count)| count)
+        .is_some_and(|(main, _)| main != position)
+}
+
+/// The champion played in at least [`OTP_SHARE`] of recent games.
+fn one_trick(games: &[MatchHistoryGame]) -> Option<i64> {
+    if games.len() < MIN_HINT_GAMES {
+        return None;
+    }
+
+    let mut counts = HashMap::new();
+    for game in games {
+        if let Some(participant) = game.participant() {
+            *counts.entry(participant.champion_id).or_insert(0_usize) += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by_key(|&(_, count)| count)
+        .filter(|&(_, count)| count as f64 / games.len() as f64 >= OTP_SHARE)
+        .map(|(champion_id, _)| champion_id)
+}
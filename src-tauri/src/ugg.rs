@@ -11,7 +11,8 @@ use ddragon::models::{
     mission_assets::MissionAsset,
     profile_icons::ProfileIcon,
     runes::RuneElement,
-    shared::{BasicDatum, HasImage},
+    shared::{BasicDatum, HasImage, Image},
+    summoner_spells::SummonerSpell,
     tft::{self, queues::Queue, regalia::RegaliaData, tactitians::Tactician},
 };
 use image::DynamicImage;
@@ -27,8 +28,10 @@ use ugg_types::{
 };
 
 use crate::{
+    cache, cdragon,
     ddragon::{Client as DdragonClient, ClientBuilder as DdragonClientBuilder},
     error::UggError,
+    rank::PreferredRanks,
 };
 
 type CacheValue<T> = HashMap<mappings::Region, HashMap<mappings::Rank, HashMap<mappings::Role, T>>>;
@@ -37,6 +40,15 @@ type OverviewCache = Cache<WrappedOverviewData>;
 type MatchupCache = Cache<WrappedMatchupData>;
 type UggApiVersions = HashMap<String, HashMap<String, String>>;
 
+/// Dragontail snapshot bundled with the app, as a `.tgz` or unpacked dir in
+/// the resource dir. Used when data dragon can't be reached, e.g. offline on
+/// first launch.
+const BUNDLED_DRAGONTAIL: [&str; 2] = ["dragontail", "dragontail.tgz"];
+
+/// Minimum number of matches before a personalized rank's stats are used over
+/// a broader rank's.
+const MIN_SAMPLE: i64 = 1000;
+
 #[derive(Debug, Clone)]
 pub struct DdragonClientWrapper {
     ddragon: DdragonClient,
@@ -54,6 +66,7 @@ pub struct Versions {
 pub struct ClientBuilder<'a> {
     version: Option<&'a str>,
     locale: Option<&'a str>,
+    realm: Option<&'a str>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +82,7 @@ pub struct RuneElementWrapper {
 #[derive(Debug, Clone)]
 pub struct Client {
     ddragon: DdragonClientWrapper,
+    cdragon: cdragon::Client,
     ugg_api_versions: UggApiVersions,
 
     version: String,
@@ -95,18 +109,44 @@ impl DdragonClientWrapper {
         app: &AppHandle<R>,
         version: Option<&str>,
         locale: Option<&str>,
+        realm: Option<&str>,
     ) -> crate::Result<Self> {
-        let mut client = DdragonClientBuilder::new().cache(app.path().app_cache_dir()?);
-        if let Some(v) = version {
-            client = client.version(v);
-        }
-        if let Some(l) = locale {
-            client = client.locale(l);
-        }
+        let builder = || -> crate::Result<DdragonClientBuilder<'_>> {
+            let mut client = DdragonClientBuilder::new().cache(cache::dir(app)?);
+            if let Some(v) = version {
+                client = client.version(v);
+            }
+            if let Some(l) = locale {
+                client = client.locale(l);
+            }
+            Ok(client)
+        };
+        let online = match realm {
+            Some(realm) => builder()?.realm(realm),
+            None => builder()?,
+        };
+        let ddragon = match online.build().await {
+            Ok(ddragon) => ddragon,
+            Err(err) => {
+                let resource_dir = app.path().resource_dir()?;
+                let Some(dragontail) = BUNDLED_DRAGONTAIL
+                    .iter()
+                    .map(|path| resource_dir.join(path))
+                    .find(|path| path.exists())
+                else {
+                    return Err(err);
+                };
+                builder()?
+                    .dragontail(dragontail)
+                    .unpack_dir(cache::dragontail_dir(app)?)
+                    .build()
+                    .await?
+            }
+        };
         let cache_size = NonZeroUsize::new(50).unwrap();
 
         Ok(Self {
-            ddragon: client.build().await?,
+            ddragon,
             overview_cache: Arc::new(Mutex::new(LruCache::new(cache_size))),
             matchup_cache: Arc::new(Mutex::new(LruCache::new(cache_size))),
         })
@@ -120,6 +160,23 @@ impl DdragonClientWrapper {
         self.ddragon.version()
     }
 
+    pub const fn locale(&self) -> &str {
+        self.ddragon.locale()
+    }
+
+    pub fn realm(&self) -> Option<&str> {
+        self.ddragon.realm()
+    }
+
+    pub async fn get_live_version(&self) -> crate::Result<String> {
+        self.ddragon.get_live_version().await
+    }
+
+    /// Whether data is read from the bundled dragontail instead of over HTTP.
+    pub const fn is_local(&self) -> bool {
+        self.ddragon.is_local()
+    }
+
     async fn get<T: DeserializeOwned, U: IntoUrl>(&self, url: U) -> crate::Result<T> {
         Ok(self
             .ddragon
@@ -131,9 +188,12 @@ impl DdragonClientWrapper {
             .await?)
     }
 
+    pub async fn get_languages(&self) -> crate::Result<Vec<String>> {
+        self.ddragon.get_languages().await
+    }
+
     pub async fn get_supported_versions(&self) -> crate::Result<Box<[String]>> {
-        self.get(self.ddragon.base_url().join("/api/versions.json")?)
-            .await
+        Ok(self.ddragon.get_versions().await?.into_boxed_slice())
     }
 
     forward_ddragon_calls!(
@@ -208,8 +268,8 @@ impl DdragonClientWrapper {
             .get_summoner_spells()
             .await?
             .data
-            .into_iter()
-            .filter_map(|(_, s)| {
+            .into_values()
+            .filter_map(|s| {
                 if let Ok(k) = s.key.parse::<i64>() {
                     Some((k, s.name))
                 } else {
@@ -219,16 +279,52 @@ impl DdragonClientWrapper {
             .collect())
     }
 
+    /// Find a summoner spell by its ID like `SummonerFlash`, or its numeric
+    /// key.
+    pub async fn get_summoner_spell(&self, id: &str) -> crate::Result<Option<SummonerSpell>> {
+        Ok(self
+            .ddragon
+            .get_summoner_spells()
+            .await?
+            .data
+            .into_values()
+            .find(|s| s.id == id || s.key == id))
+    }
+
+    /// Icon path of a rune or rune style, relative to `/cdn/img/`.
+    pub async fn get_rune_icon(&self, id: i64) -> crate::Result<Option<String>> {
+        Ok(self
+            .ddragon
+            .get_runes()
+            .await?
+            .into_iter()
+            .find_map(|style| {
+                if style.id == id {
+                    return Some(style.icon);
+                }
+                style
+                    .slots
+                    .into_iter()
+                    .flat_map(|slot| slot.runes)
+                    .find(|rune| rune.id == id)
+                    .map(|rune| rune.icon)
+            }))
+    }
+
     pub async fn get_champion(&self, id: &str) -> crate::Result<Box<Champion>> {
         self.ddragon.get_champion(id).await
     }
 
+    pub async fn get_cdn_image(&self, path: &str) -> crate::Result<DynamicImage> {
+        self.ddragon.get_cdn_image(path).await
+    }
+
     pub async fn get_image_of<T: HasImage + Sync>(&self, item: &T) -> crate::Result<DynamicImage> {
         self.ddragon.get_image_of(item).await
     }
 
-    pub async fn get_sprite_of<T: HasImage + Sync>(&self, item: &T) -> crate::Result<DynamicImage> {
-        self.ddragon.get_sprite_of(item).await
+    pub async fn get_sprite_of(&self, image: &Image) -> crate::Result<DynamicImage> {
+        self.ddragon.get_sprite_of(image).await
     }
 
     pub async fn get_ugg_api_versions(&self) -> crate::Result<UggApiVersions> {
@@ -241,31 +337,18 @@ impl Client {
         app: &AppHandle<R>,
         version: Option<&str>,
         locale: Option<&str>,
+        realm: Option<&str>,
     ) -> crate::Result<Self> {
-        let mut ddragon = DdragonClientWrapper::new(app, version, locale).await?;
+        let mut ddragon = DdragonClientWrapper::new(app, version, locale, realm).await?;
         let mut version = ddragon.version().to_owned();
-        let ddragon_versions = ddragon.get_supported_versions().await?;
-        let ugg_api_versions = ddragon.get_ugg_api_versions().await?;
-        let supported_versions = ddragon_versions
-            .into_iter()
-            .filter_map(|v| {
-                let ugg = v.split('.').take(2).collect::<Box<[_]>>().join("_");
-                // BUG: https://github.com/rust-lang/rust-clippy/issues/14449
-                #[allow(clippy::map_entry)]
-                if ugg_api_versions.contains_key(&ugg) {
-                    Some(Versions { ddragon: v, ugg })
-                } else {
-                    None
-                }
-            })
-            .collect::<Box<[_]>>();
+        let (supported_versions, ugg_api_versions) = Self::supported_versions(&ddragon).await?;
 
         if let Some(v) = supported_versions.first() {
             if !supported_versions
                 .iter()
                 .any(|Versions { ddragon, .. }| ddragon == &version)
             {
-                ddragon = DdragonClientWrapper::new(app, Some(&v.ddragon), locale).await?;
+                ddragon = DdragonClientWrapper::new(app, Some(&v.ddragon), locale, realm).await?;
                 ddragon.version().clone_into(&mut version);
             }
         } else {
@@ -276,13 +359,20 @@ impl Client {
         let items = ddragon.get_items().await?;
         let runes = ddragon.get_runes().await?;
         let summoner_spells = ddragon.get_summoner_spells().await?;
+        let cdragon = cdragon::ClientBuilder::new()
+            .cache(cache::dir(app)?)
+            .ddragon(&ddragon.ddragon)
+            .build()
+            .await?;
 
         let mut version_split = version.split('.').collect::<Vec<_>>();
         version_split.remove(version_split.len() - 1);
         let patch_version = version_split.join("_");
+        cache::prune(app, &version);
 
         Ok(Self {
             ddragon,
+            cdragon,
             ugg_api_versions,
 
             version,
@@ -295,10 +385,55 @@ impl Client {
         })
     }
 
+    /// ddragon versions with u.gg stats, newest first.
+    async fn supported_versions(
+        ddragon: &DdragonClientWrapper,
+    ) -> crate::Result<(Box<[Versions]>, UggApiVersions)> {
+        let ddragon_versions = ddragon.get_supported_versions().await?;
+        let ugg_api_versions = match ddragon.get_ugg_api_versions().await {
+            Ok(versions) => versions,
+            // Offline, trust the bundled versions.
+            Err(_) if ddragon.is_local() => UggApiVersions::new(),
+            Err(err) => return Err(err),
+        };
+        let supported_versions = ddragon_versions
+            .into_iter()
+            .filter_map(|v| {
+                let ugg = v.split('.').take(2).collect::<Box<[_]>>().join("_");
+                // BUG: https://github.com/rust-lang/rust-clippy/issues/14449
+                #[allow(clippy::map_entry)]
+                if ugg_api_versions.contains_key(&ugg) || ugg_api_versions.is_empty() {
+                    Some(Versions { ddragon: v, ugg })
+                } else {
+                    None
+                }
+            })
+            .collect::<Box<[_]>>();
+
+        Ok((supported_versions, ugg_api_versions))
+    }
+
+    /// The live ddragon version if it has u.gg stats, or else the newest one
+    /// that does, fetched again rather than from when the client was built.
+    pub async fn latest_supported_version(&self) -> crate::Result<Option<String>> {
+        let live = self.ddragon.get_live_version().await?;
+        let (versions, _) = Self::supported_versions(&self.ddragon).await?;
+        Ok(versions
+            .iter()
+            .find(|v| v.ddragon == live)
+            .or_else(|| versions.first())
+            .map(|v| v.ddragon.clone()))
+    }
+
     pub fn ddragon(&self) -> DdragonClientWrapper {
         self.ddragon.clone()
     }
 
+    /// CommunityDragon data of the same patch as [`Self::ddragon`].
+    pub const fn cdragon(&self) -> &cdragon::Client {
+        &self.cdragon
+    }
+
     pub const fn version(&self) -> &str {
         self.version.as_str()
     }
@@ -333,6 +468,29 @@ impl Client {
         }
     }
 
+    /// Find a champion by its ID, like `Ahri`.
+    pub fn champion(&self, id: &str) -> Option<&ChampionShort> {
+        self.champions.get(id)
+    }
+
+    pub fn item(&self, id: &str) -> Option<&Item> {
+        self.items.get(id)
+    }
+
+    pub const fn champions(&self) -> &HashMap<String, ChampionShort> {
+        &self.champions
+    }
+
+    pub const fn items(&self) -> &HashMap<String, Item> {
+        &self.items
+    }
+
+    /// Find a champion by its numeric key, as used by the LCU API.
+    pub fn champion_by_key(&self, key: i64) -> Option<&ChampionShort> {
+        let key = key.to_string();
+        self.champions.values().find(|champ| champ.key == key)
+    }
+
     fn ugg_api_version<'a>(
         api_versions: &'a UggApiVersions,
         patch: &'a str,
@@ -344,21 +502,25 @@ impl Client {
         }
     }
 
+    /// Get u.gg stats for the first rank in `ranks` with data for `role`, or
+    /// the most popular role if there's no data for `role`. Personalized ranks
+    /// need at least [`MIN_SAMPLE`] matches, and if none has enough, the rank
+    /// with the most matches is used.
     #[allow(clippy::too_many_arguments)]
-    async fn get_ugg_data<WrappedData, B, KeyFn, MapFn, Data>(
+    async fn get_ugg_data<WrappedData, MatchesFn, MapFn, Data>(
         &self,
         cache: &Cache<WrappedData>,
         cache_key: String,
         path: &str,
         region: mappings::Region,
         role: mappings::Role,
-        max_by_key: KeyFn,
+        ranks: &PreferredRanks,
+        matches: MatchesFn,
         map: MapFn,
-    ) -> crate::Result<(Data, mappings::Role)>
+    ) -> crate::Result<(Data, mappings::Role, mappings::Rank)>
     where
-        WrappedData: Clone + DeserializeOwned,
-        B: Ord,
-        KeyFn: FnMut(&(&mappings::Role, &WrappedData)) -> B,
+        WrappedData: Clone + DeserializeOwned + Send,
+        MatchesFn: Fn(&WrappedData) -> i64,
         MapFn: FnOnce((&mappings::Role, &WrappedData)) -> (Data, mappings::Role),
     {
         let data = {
@@ -373,27 +535,39 @@ impl Client {
             lock.put(cache_key, data.clone());
             data
         };
-        let data_by_role = mappings::Rank::preferred_order()
+        let region_data = data.get(&region).ok_or(UggError::MissingRegionOrRank)?;
+        let candidates = ranks
+            .ranks
             .iter()
-            .find_map(|rank| {
-                data.get(&region)
-                    .and_then(|region_data| region_data.get(rank))
+            .filter_map(|rank| {
+                let data_by_role = region_data.get(rank)?;
+                let entry = data_by_role
+                    .get_key_value(&role)
+                    .or_else(|| data_by_role.iter().max_by_key(|(_, data)| matches(data)))?;
+                Some((*rank, entry))
             })
-            .ok_or(UggError::MissingRegionOrRank)?;
+            .collect::<Box<[_]>>();
+        if candidates.is_empty() {
+            return Err(UggError::MissingRegionOrRank.into());
+        }
 
-        Ok(data_by_role
-            .get_key_value(&role)
-            .or_else(|| {
-                data_by_role
-                    .iter()
-                    .max_by_key(max_by_key)
-                    .map(|(role, _)| role)
-                    .and_then(|r| data_by_role.get_key_value(r))
-            })
-            .map(map)
-            .ok_or(UggError::MissingRole)?)
+        let (rank, entry) = if ranks.personalized {
+            candidates
+                .iter()
+                .find(|(_, (_, data))| matches(data) >= MIN_SAMPLE)
+                .or_else(|| candidates.iter().max_by_key(|(_, (_, data))| matches(data)))
+        } else {
+            candidates.first()
+        }
+        .copied()
+        .ok_or(UggError::MissingRole)?;
+        let (data, role) = map(entry);
+
+        Ok((data, role, rank))
     }
 
+    /// Get the u.gg overview of `champ`, preferring ranks in the order of
+    /// `ranks`. Returns the role and rank the overview is for.
     #[allow(clippy::too_many_arguments)]
     pub async fn get_overview(
         &self,
@@ -402,7 +576,8 @@ impl Client {
         region: mappings::Region,
         mode: mappings::Mode,
         build: mappings::Build,
-    ) -> crate::Result<(Overview, mappings::Role)> {
+        ranks: &PreferredRanks,
+    ) -> crate::Result<(Overview, mappings::Role, mappings::Rank)> {
         let version =
             Self::ugg_api_version(&self.ugg_api_versions, &self.patch_version, "overview");
         let path = [
@@ -420,19 +595,23 @@ impl Client {
             &path,
             region,
             role,
-            |(_, data)| data.data.matches(),
+            ranks,
+            |data| data.data.matches(),
             |(role, data)| (data.data.clone(), *role),
         )
         .await
     }
 
+    /// Get the u.gg matchups of `champ`, preferring ranks in the order of
+    /// `ranks`. Returns the role and rank the matchups are for.
     pub async fn get_matchups(
         &self,
         champ: &ChampionShort,
         role: mappings::Role,
         region: mappings::Region,
         mode: mappings::Mode,
-    ) -> crate::Result<(MatchupData, mappings::Role)> {
+        ranks: &PreferredRanks,
+    ) -> crate::Result<(MatchupData, mappings::Role, mappings::Rank)> {
         let version =
             Self::ugg_api_version(&self.ugg_api_versions, &self.patch_version, "matchups");
         let path = [
@@ -450,7 +629,8 @@ impl Client {
             &path,
             region,
             role,
-            |(_, data)| data.data.total_matches,
+            ranks,
+            |data| i64::from(data.data.total_matches),
             |(role, data)| (data.data.clone(), *role),
         )
         .await
@@ -462,6 +642,7 @@ impl<'a> ClientBuilder<'a> {
         Self {
             version: None,
             locale: None,
+            realm: None,
         }
     }
 
@@ -475,7 +656,13 @@ impl<'a> ClientBuilder<'a> {
         self
     }
 
+    /// See [`DdragonClientBuilder::realm`].
+    pub const fn realm(mut self, realm: &'a str) -> Self {
+        self.realm = Some(realm);
+        self
+    }
+
     pub async fn build<R: Runtime>(self, app: &AppHandle<R>) -> crate::Result<Client> {
-        Client::new(app, self.version, self.locale).await
+        Client::new(app, self.version, self.locale, self.realm).await
     }
 }
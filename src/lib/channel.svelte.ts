@@ -4,8 +4,9 @@ export const api = $state<{
   channel?: Channel<KruggMessage>
   champs?: ChampionShort[]
   champ?: Champion
-  overview?: { overview: OverviewData; role: Role }
-  matchups?: { matchups: MatchupData; role: Role }
+  overview?: { overview: OverviewData; role: Role; rank: Rank }
+  matchups?: { matchups: MatchupData; role: Role; rank: Rank }
+  aramBench?: BenchRanking
 }>({})
 
 export const newChannel = () => {
@@ -27,6 +28,9 @@ export const newChannel = () => {
         break
       case 'matchups':
         api.matchups = data
+        break
+      case 'aramBench':
+        api.aramBench = data
     }
   }
 
@@ -47,6 +51,8 @@ export type KruggMessage =
       data: {
         overview: OverviewData
         role: Role
+        /** Rank bracket the stats are from. */
+        rank: Rank
       }
     }
   | {
@@ -54,8 +60,14 @@ export type KruggMessage =
       data: {
         matchups: MatchupData
         role: Role
+        /** Rank bracket the stats are from. */
+        rank: Rank
       }
     }
+  | {
+      type: 'aramBench'
+      data: BenchRanking
+    }
 
 export enum Role {
   Jungle = 1,
@@ -70,6 +82,25 @@ export enum Role {
   Lane,
 }
 
+export enum Rank {
+  Challenger = 1,
+  Master,
+  Diamond,
+  Platinum,
+  Gold,
+  Silver,
+  Bronze,
+  Overall,
+  PlatinumPlus = 10,
+  DiamondPlus,
+  Iron,
+  Grandmaster,
+  MasterPlus,
+  Diamond2Plus,
+  Emerald,
+  EmeraldPlus,
+}
+
 export interface ChampionShort {
   version: string
   id: string
@@ -234,3 +265,20 @@ export interface Passive {
   description: string
   image: Image
 }
+
+export interface BenchEntry {
+  championId: number
+  name: string
+  winRate: number | null
+  matches: number
+  owned: boolean
+  /** Whether this is the local player's current champion. */
+  current: boolean
+}
+
+export interface BenchRanking {
+  /** Current and bench champions, sorted by win rate descending. */
+  entries: BenchEntry[]
+  /** Bench champion with a clearly better win rate than the current one. */
+  suggestedSwap: number | null
+}
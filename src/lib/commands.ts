@@ -1,6 +1,7 @@
 import { invoke } from '@tauri-apps/api/core'
+import type { Summoner } from 'tauri-plugin-lcu-api'
 
-import { newChannel } from '$lib'
+import { newChannel, type AutoPick, type Image } from '$lib'
 
 export const showMainWindow = async () => {
   await invoke<null>('show_main_window')
@@ -13,3 +14,482 @@ export const getChampions = async () => {
 export const getChampion = async (id: string) => {
   await invoke<null>('get_champion', { channel: newChannel(), id })
 }
+
+/** Get the u.gg overview of the champion `name`. The mode defaults to the current queue's. */
+export const getOverview = async (
+  name: string,
+  options: { role?: string; mode?: string; build?: string } = {},
+) => {
+  await invoke<null>('get_overview', { channel: newChannel(), name, ...options })
+}
+
+/** Get the u.gg matchups of the champion `name`. The mode defaults to the current queue's. */
+export const getMatchups = async (name: string, options: { role?: string; mode?: string } = {}) => {
+  await invoke<null>('get_matchups', { channel: newChannel(), name, ...options })
+}
+
+export const getAramBench = async () => {
+  await invoke<null>('get_aram_bench', { channel: newChannel() })
+}
+
+export const exportSettingsProfile = async (name: string) => {
+  await invoke<null>('export_settings_profile', { name })
+}
+
+export const listSettingsProfiles = async () => {
+  return await invoke<string[]>('list_settings_profiles')
+}
+
+export const diffSettingsProfiles = async (left: string, right: string) => {
+  return await invoke<SettingDiff[]>('diff_settings_profiles', { left, right })
+}
+
+export const applySettingsProfile = async (name: string) => {
+  await invoke<null>('apply_settings_profile', { name })
+}
+
+/** A setting that differs between two profiles. */
+export interface SettingDiff {
+  /** Dot separated path to the setting. */
+  path: string
+  left: unknown
+  right: unknown
+}
+
+export const getLootAdvice = async () => {
+  return await invoke<LootAdvice[]>('get_loot_advice')
+}
+
+/** Craft a loot item. Only call after the user confirms the action. */
+export const craftLoot = async (lootId: string, action: LootAction, repeat: number) => {
+  await invoke<null>('craft_loot', { lootId, action, repeat })
+}
+
+export type LootAction = 'upgrade' | 'disenchant' | 'keep'
+
+export interface LootAdvice {
+  lootId: string
+  championId: number
+  name: string
+  count: number
+  action: LootAction
+  /** Number of shards the action applies to. */
+  quantity: number
+  /** Blue essence gained (positive) or spent (negative) by the action. */
+  blueEssence: number
+  reason: 'championPool' | 'highMastery' | 'notPlayed' | 'owned'
+}
+
+/**
+ * Summarize the players in champ select, and the enemy team once the game is
+ * loading.
+ */
+export const scoutPlayers = async () => {
+  return await invoke<PlayerSummary[]>('scout_players')
+}
+
+export interface PlayerSummary {
+  puuid: string
+  gameName: string
+  tagLine: string
+  ally: boolean
+  /** Lowercase position, e.g. `top` or `utility`, or empty if unknown. */
+  position: string
+  championId: number
+  championName: string | null
+  /** Solo queue rank, if ranked. */
+  rank: RankSummary | null
+  /** `null` if the player's summoner ID is hidden, like other players' in champ select. */
+  championMasteryLevel: number | null
+  championMasteryPoints: number | null
+  /** Recent games played on the picked champion. */
+  championGames: number
+  recentGames: number
+  recentWinRate: number | null
+  /** The player is playing a position they rarely play. */
+  autofill: boolean
+  /** Champion the player almost exclusively plays. */
+  oneTrick: number | null
+}
+
+export interface RankSummary {
+  tier: string
+  division: string
+  leaguePoints: number
+  wins: number
+  losses: number
+}
+
+/** Look up a player by their Riot ID, formatted as `name#tag`. */
+export const lookupPlayer = async (riotId: string) => {
+  return await invoke<ProfileBundle>('lookup_player', { riotId })
+}
+
+export interface ProfileBundle {
+  summoner: Summoner
+  profileIcon: { id: number; image: Image } | null
+  /** Ranked stats for every queue the player is ranked in. */
+  ranked: RankedQueueStats[]
+  /** Highest mastery champions, sorted by points descending. */
+  topMastery: ChampionMastery[]
+}
+
+export interface RankedQueueStats {
+  queueType: string
+  tier: string
+  division: string
+  leaguePoints: number
+  wins: number
+  losses: number
+  isProvisional: boolean
+  highestTier: string
+  previousSeasonEndTier: string
+}
+
+export interface ChampionMastery {
+  championId: number
+  championLevel: number
+  championPoints: number
+  lastPlayTime: number
+  puuid: string
+}
+
+/**
+ * Get the current summoner's and their friends' presence, with friends grouped
+ * by status.
+ */
+export const getFriends = async () => {
+  return await invoke<FriendsList>('get_friends')
+}
+
+/** Get the build for the champion the friend with `puuid` is playing. */
+export const getFriendBuild = async (puuid: string) => {
+  await invoke<null>('get_friend_build', { channel: newChannel(), puuid })
+}
+
+export type FriendStatus =
+  | 'inGame'
+  | 'champSelect'
+  | 'inQueue'
+  | 'online'
+  | 'away'
+  | 'mobile'
+  | 'offline'
+
+export interface FriendSummary {
+  puuid: string
+  gameName: string
+  tagLine: string
+  status: FriendStatus
+  statusMessage: string
+  championId: number | null
+  championName: string | null
+  queueId: number | null
+}
+
+export interface FriendsList {
+  me: FriendSummary
+  friends: Partial<Record<FriendStatus, FriendSummary[]>>
+}
+
+export interface RegionLocale {
+  /** u.gg region ID, e.g. `"1"` for NA. */
+  region: string
+  /** ddragon locale, e.g. `en_US`. */
+  locale: string
+}
+
+/** Get the user's auto pick preferences. */
+export const getAutoPick = async () => {
+  return await invoke<AutoPick>('get_auto_pick')
+}
+
+/** Save the user's auto pick preferences. Returns the saved preferences. */
+export const setAutoPick = async (preferences: AutoPick) => {
+  return await invoke<AutoPick>('set_auto_pick', { preferences })
+}
+
+/** Get the region of stats requests and the locale of champion data. */
+export const getRegionLocale = async () => {
+  return await invoke<RegionLocale>('get_region_locale')
+}
+
+/** Change the locale of champion data, e.g. `en_US`, and save it as the user's setting. */
+export const setLocale = async (locale: string) => {
+  return await invoke<RegionLocale>('set_locale', { locale })
+}
+
+/**
+ * Change the region of stats requests to an LCU region, e.g. `EUW`, and save it as the user's
+ * setting. Goes back to the detected region if omitted.
+ */
+export const setRegion = async (region?: string) => {
+  return await invoke<RegionLocale>('set_region', { region })
+}
+
+/** Ranked stats in one queue at a point in time. */
+export interface LpEntry {
+  /** Unix timestamp in milliseconds. */
+  timestamp: number
+  /** The game that caused the change, or `null` if it happened while krugg wasn't running. */
+  gameId: number | null
+  tier: string
+  division: string
+  leaguePoints: number
+  wins: number
+  losses: number
+  /** LP counted from Iron IV 0 LP, for charting across divisions. */
+  totalLp: number
+}
+
+export interface LpStreaks {
+  /** Positive for a win streak, negative for a loss streak. */
+  current: number
+  longestWin: number
+  longestLoss: number
+}
+
+/** Get the current summoner's LP history, oldest first. Defaults to ranked solo/duo. */
+export const getLpHistory = async (queueType?: string) => {
+  return await invoke<LpEntry[]>('get_lp_history', { queueType })
+}
+
+/** Get the current summoner's win and loss streaks. Defaults to ranked solo/duo. */
+export const getLpStreaks = async (queueType?: string) => {
+  return await invoke<LpStreaks>('get_lp_streaks', { queueType })
+}
+
+export interface MomentPlayer {
+  /** Name to lock the replay camera onto, see `replayLockCamera`. */
+  name: string
+  championName: string | null
+}
+
+export interface Moment {
+  /** Seconds since the game started. */
+  time: number
+  kind: 'kill' | 'monster' | 'building'
+  killer: MomentPlayer | null
+  victim: MomentPlayer | null
+  /** Monster or building type, e.g. `BARON_NASHOR` or `TOWER_BUILDING`. */
+  target: string | null
+}
+
+export interface Playback {
+  paused: boolean
+  seeking: boolean
+  /** Seconds since the game started. */
+  time: number
+  speed: number
+  /** Length of the replay in seconds. */
+  length: number
+}
+
+export interface Render {
+  cameraMode: string
+  cameraAttached: boolean
+  selectionName: string
+  [key: string]: unknown
+}
+
+/** Get the kills, objectives, and buildings of a game, to seek its replay to. */
+export const getReplayMoments = async (gameId: number) => {
+  return await invoke<Moment[]>('get_replay_moments', { gameId })
+}
+
+/** Seek the open replay to `time`, in seconds since the game started. */
+export const replaySeek = async (time: number) => {
+  return await invoke<Playback>('replay_seek', { time })
+}
+
+/** Set the playback speed of the open replay. */
+export const replaySetSpeed = async (speed: number) => {
+  return await invoke<Playback>('replay_set_speed', { speed })
+}
+
+/** Pause or resume the open replay. */
+export const replaySetPaused = async (paused: boolean) => {
+  return await invoke<Playback>('replay_set_paused', { paused })
+}
+
+/** Lock the camera of the open replay onto the player `name`, or unlock it if omitted. */
+export const replayLockCamera = async (name?: string) => {
+  return await invoke<Render>('replay_lock_camera', { name })
+}
+
+export interface PatchUsage {
+  /** Like `15.7`, or `null` for responses of no patch. */
+  patch: string | null
+  /** Size in bytes. */
+  size: number
+  entries: number
+}
+
+export interface CacheUsage {
+  /** Total size in bytes. */
+  size: number
+  /** Max total size in bytes. */
+  cap: number
+  /** Newest patch first, then responses of no patch. */
+  patches: PatchUsage[]
+  /** Size of the unpacked dragontail in bytes, not part of `size`. */
+  dragontail: number
+}
+
+/** Get the size of the HTTP cache per patch. */
+export const getCacheUsage = async () => {
+  return await invoke<CacheUsage>('get_cache_usage')
+}
+
+/** Clear the HTTP cache of `patch`, like `15.7`, or all of it if omitted. Returns the remaining usage. */
+export const clearCache = async (patch?: string) => {
+  return await invoke<CacheUsage>('clear_cache', { patch })
+}
+
+export type AtlasKind = 'champion' | 'item'
+
+export interface AtlasRect {
+  x: number
+  y: number
+  w: number
+  h: number
+}
+
+export interface AtlasIndex {
+  /** ddragon version of the icons. */
+  version: string
+  width: number
+  height: number
+  /** Champion ID like `Ahri`, or item ID like `3089`, to its rect. */
+  icons: Record<string, AtlasRect>
+}
+
+/**
+ * Get the icon coordinates in the `kind` sprite atlas, whose image is `ddragonUrl('atlas/{kind}')`.
+ */
+export const getSpriteAtlas = async (kind: AtlasKind) => {
+  return await invoke<AtlasIndex>('get_sprite_atlas', { kind })
+}
+
+export interface CdragonSpell {
+  /** `q`, `w`, `e`, or `r`. */
+  spellKey: string
+  name: string
+  abilityIconPath: string
+  description: string
+  dynamicDescription: string
+  maxLevel: number
+  cooldownCoefficients: number[]
+  costCoefficients: number[]
+  range: number[]
+  effectAmounts: Record<string, number[]>
+}
+
+export interface CdragonChroma {
+  id: number
+  name: string
+  chromaPath: string
+  colors: string[]
+}
+
+export interface CdragonSkin {
+  id: number
+  isBase: boolean
+  name: string
+  splashPath: string
+  uncenteredSplashPath: string
+  tilePath: string
+  loadScreenPath: string
+  rarity: string
+  isLegacy: boolean
+  chromas: CdragonChroma[]
+}
+
+export interface CdragonChampion {
+  id: number
+  name: string
+  alias: string
+  title: string
+  shortBio: string
+  roles: string[]
+  squarePortraitPath: string
+  passive: { name: string; abilityIconPath: string; description: string }
+  spells: CdragonSpell[]
+  skins: CdragonSkin[]
+}
+
+export interface CharacterRecord {
+  characterName: string
+  baseHp: number
+  hpPerLevel: number
+  baseStaticHpRegen: number
+  baseArmor: number
+  armorPerLevel: number
+  baseSpellBlock: number
+  spellBlockPerLevel: number
+  baseMoveSpeed: number
+  attackRange: number
+  attackSpeed: number
+  attackSpeedRatio: number
+  attackSpeedPerLevel: number
+  baseDamage: number
+  damagePerLevel: number
+  spellNames: string[]
+}
+
+export interface AbilityObject {
+  scriptName: string
+  spell: {
+    cooldownTime: number[]
+    castRange: number[]
+    mana: number[]
+    dataValues: { name: string; values: number[] }[]
+  }
+}
+
+export interface ChampionDetails {
+  champion: CdragonChampion
+  stats?: CharacterRecord
+  abilities: AbilityObject[]
+}
+
+/**
+ * Get the CommunityDragon abilities, skins and base stats of the champion `id`, like `Ahri`. Asset
+ * paths like `abilityIconPath` are shown with `ddragonUrl('cdragon' + path)`.
+ */
+export const getChampionDetails = async (id: string) => {
+  return await invoke<ChampionDetails>('get_champion_details', { id })
+}
+
+export interface Perk {
+  id: number
+  name: string
+  majorChangePatchVersion: string
+  tooltip: string
+  shortDesc: string
+  longDesc: string
+  iconPath: string
+  endOfGameStatDescs: string[]
+}
+
+/** Get the runes as shown in the client. */
+export const getPerks = async () => {
+  return await invoke<Perk[]>('get_perks')
+}
+
+export interface Augment {
+  id: number
+  apiName: string
+  name: string
+  desc: string
+  tooltip: string
+  iconLarge: string
+  iconSmall: string
+  /** 0 silver, 1 gold, 2 prismatic. */
+  rarity: number
+  dataValues: Record<string, number>
+}
+
+export const getArenaAugments = async () => {
+  return await invoke<Augment[]>('get_arena_augments')
+}
@@ -1,14 +1,19 @@
 import { listen, type EventCallback } from '@tauri-apps/api/event'
-import type { LockFile } from 'tauri-plugin-lcu-api'
+import type { Instance, LockFile } from 'tauri-plugin-lcu-api'
+
+import { api } from './channel.svelte'
+import { getChampions, type RegionLocale } from './commands'
 
 export const lcu = $state<{
   connected: boolean
   lockFile: LockFile | null
   baseUrl: string | null
+  instances: Instance[]
 }>({
   connected: false,
   lockFile: null,
   baseUrl: null,
+  instances: [],
 })
 
 const applyPayload = <K extends keyof typeof lcu>(key: K) => {
@@ -17,9 +22,27 @@ const applyPayload = <K extends keyof typeof lcu>(key: K) => {
   }) satisfies EventCallback<(typeof lcu)[K]>
 }
 
+/** Payload of `patch-changed`. */
+export interface PatchChanged {
+  /** ddragon version like `15.7.1`. */
+  old: string
+  new: string
+}
+
+const reloadChampions = async () => {
+  api.champ = undefined
+  if (api.champs !== undefined) {
+    await getChampions()
+  }
+}
+
 export const listenAll = () =>
   Promise.all([
     listen<boolean>('lcu-connected', applyPayload('connected')),
     listen<LockFile>('lcu-lockfile', applyPayload('lockFile')),
     listen<string>('lcu-base-url', applyPayload('baseUrl')),
+    listen<Instance[]>('lcu-instances', applyPayload('instances')),
+    // Reload champion data in the new locale or patch.
+    listen<RegionLocale>('locale-changed', reloadChampions),
+    listen<PatchChanged>('patch-changed', reloadChampions),
   ])
@@ -1,4 +1,5 @@
 import { error as svelteError } from '@sveltejs/kit'
+import { convertFileSrc } from '@tauri-apps/api/core'
 
 export * from './channel.svelte'
 export * from './commands'
@@ -10,6 +11,22 @@ export const error = {
   getChampions: () => svelteError(500, 'Failed to load champion data'),
 } as const
 
+/**
+ * URL of a data dragon image served by krugg, which works offline. `path` is `{kind}/{id}`, e.g.
+ * `champion/Ahri`, `item/3089`, `perk/8112`, `spell/SummonerFlash`, `profileicon/29`,
+ * `splash/Ahri/1`, or `atlas/champion` for the sprite atlas indexed by `getSpriteAtlas`.
+ */
+export const ddragonUrl = (
+  path: string,
+  options: { size?: number; format?: 'png' | 'webp' } = {},
+) => {
+  const query = new URLSearchParams()
+  if (options.size !== undefined) query.set('size', options.size.toString())
+  if (options.format !== undefined) query.set('format', options.format)
+  const url = convertFileSrc(path, 'ddragon')
+  return query.size > 0 ? `${url}?${query}` : url
+}
+
 export const themes = ['system', 'light', 'dark'] as const
 export type Theme = (typeof themes)[number]
 
@@ -1,5 +1,5 @@
 import { Store } from '@tauri-apps/plugin-store'
-import type { Theme } from '$lib'
+import type { LpEntry, Theme } from '$lib'
 
 export const appState = $state<{
   theme: Theme
@@ -10,8 +10,38 @@ export const appState = $state<{
 export type AppData = Partial<{
   lockfile_path: string
   theme: Theme
+  aram_auto_swap: boolean
+  champion_pool: number[]
+  /** LCU region, e.g. `EUW`. Overrides the League client's region. */
+  region: string
+  /** ddragon locale, e.g. `en_US`. Overrides the League client's locale. */
+  locale: string
+  client_region: string
+  client_locale: string
+  /** Use u.gg stats from the local player's ranked tier. */
+  personalized_rank: boolean
+  auto_pick: AutoPick
+  /** LP history by puuid, then by queue type, see `getLpHistory`. */
+  lp_history: Record<string, Record<string, LpEntry[]>>
 }>
 
+/** Champion IDs in order of preference. */
+export interface AutoPickChampions {
+  picks: number[]
+  bans: number[]
+}
+
+export const autoPickPositions = ['any', 'top', 'jungle', 'middle', 'bottom', 'utility'] as const
+export type AutoPickPosition = (typeof autoPickPositions)[number]
+
+export interface AutoPick {
+  enabled: boolean
+  /** Lock in picks and bans instead of only hovering them. */
+  lock: boolean
+  /** Keyed by assigned position. `any` is used for blind pick and positions without preferences. */
+  positions: Partial<Record<AutoPickPosition, AutoPickChampions>>
+}
+
 const stores = $state<{
   appData: Store | null
 }>({
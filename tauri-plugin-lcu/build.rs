@@ -1,10 +1,20 @@
 #![deny(clippy::all, clippy::nursery, rust_2018_idioms)]
 
 #[cfg(not(feature = "ugg-types"))]
-const COMMANDS: &[&str] = &["connected"];
+const COMMANDS: &[&str] = &[
+    "connected",
+    "instances",
+    "set_active_instance",
+    "diagnose",
+    "get_summoner_by_riot_id",
+];
 #[cfg(feature = "ugg-types")]
 const COMMANDS: &[&str] = &[
     "connected",
+    "instances",
+    "set_active_instance",
+    "diagnose",
+    "get_summoner_by_riot_id",
     "get_current_summoner",
     "get_current_rune_page",
     "update_rune_page",
@@ -1,4 +1,4 @@
-import { invoke } from '@tauri-apps/api/core'
+import { convertFileSrc, invoke } from '@tauri-apps/api/core'
 
 export interface LockFile {
   /** Path to the lockfile. */
@@ -13,6 +13,49 @@ export interface LockFile {
   authHeader: string
 }
 
+/** Install dir of a League client instance, e.g. live or PBE. */
+export type InstanceId = string
+
+export interface Instance {
+  id: InstanceId
+  lockfile: LockFile
+  baseUrl: string
+  /** Whether requests go to this instance by default. */
+  active: boolean
+}
+
+/** Result of a diagnostic step. `skipped` if a step it depends on failed. */
+export type Check<T> =
+  | { status: 'passed'; value: T }
+  | { status: 'failed'; error: string }
+  | { status: 'skipped' }
+
+export interface Diagnostics {
+  locators: LocatorDiagnostics[]
+  /** One per lockfile path found by the locators. */
+  lockfiles: LockFileDiagnostics[]
+}
+
+export interface LocatorDiagnostics {
+  locator: 'store' | 'process' | 'path'
+  /** Lockfile paths found by the locator. */
+  paths: Check<string[]>
+}
+
+export interface LockFileDiagnostics {
+  path: string
+  /** Whether the plugin is connected to this lockfile's instance. */
+  connected: boolean
+  lockfile: Check<{ pid: number; port: number }>
+  /** Whether the process that wrote the lockfile is running. */
+  process: Check<null>
+  tcp: Check<null>
+  /** TLS handshake, with the status of an unauthenticated request. */
+  tls: Check<number>
+  /** Status of an authenticated request. */
+  request: Check<number>
+}
+
 export interface ClientSummoner {
   accountId: number
   displayName: string
@@ -37,6 +80,18 @@ export interface RerollPoints {
   pointsToReroll: number
 }
 
+export interface Summoner {
+  accountId: number
+  summonerId: number
+  puuid: string
+  gameName: string
+  tagLine: string
+  profileIconId: number
+  summonerLevel: number
+  /** `PUBLIC` or `PRIVATE`. */
+  privacy: string
+}
+
 export interface RunePage {
   current: boolean
   id: number
@@ -59,18 +114,50 @@ export interface NewRunePage {
   subStyleId: number
 }
 
-export const connected = async () => {
-  return await invoke<boolean>('plugin:lcu|connected')
+/**
+ * URL of a game data asset like `/lol-game-data/assets/v1/champion-icons/1.png`,
+ * served from the active instance through the `lcu-asset://` URI scheme.
+ */
+export const assetUrl = (path: string) => convertFileSrc(path, 'lcu-asset')
+
+// Commands take an optional `instance`, defaulting to the active instance.
+
+export const connected = async (instance?: InstanceId) => {
+  return await invoke<boolean>('plugin:lcu|connected', { instance })
+}
+
+/** Get all connected League client instances. */
+export const instances = async () => {
+  return await invoke<Instance[]>('plugin:lcu|instances')
+}
+
+/** Send requests to `instance` by default. */
+export const setActiveInstance = async (instance: InstanceId) => {
+  return await invoke<null>('plugin:lcu|set_active_instance', { instance })
+}
+
+/** Run every step of finding and connecting to the LCU and report the result of each. */
+export const diagnose = async () => {
+  return await invoke<Diagnostics>('plugin:lcu|diagnose')
+}
+
+/** Get a summoner by their Riot ID, formatted as `name#tag`. */
+export const getSummonerByRiotId = async (riotId: string, instance?: InstanceId) => {
+  return await invoke<Summoner>('plugin:lcu|get_summoner_by_riot_id', { riotId, instance })
 }
 
-export const getCurrentSummoner = async () => {
-  return await invoke<ClientSummoner>('plugin:lcu|get_current_summoner')
+export const getCurrentSummoner = async (instance?: InstanceId) => {
+  return await invoke<ClientSummoner>('plugin:lcu|get_current_summoner', { instance })
 }
 
-export const getCurrentRunePage = async () => {
-  return await invoke<RunePage>('plugin:lcu|get_current_rune_page')
+export const getCurrentRunePage = async (instance?: InstanceId) => {
+  return await invoke<RunePage>('plugin:lcu|get_current_rune_page', { instance })
 }
 
-export const updateRunePage = async (pageId: number, runePage: NewRunePage) => {
-  return await invoke<null>('plugin:lcu|update_rune_page', { pageId, runePage })
+export const updateRunePage = async (
+  pageId: number,
+  runePage: NewRunePage,
+  instance?: InstanceId,
+) => {
+  return await invoke<null>('plugin:lcu|update_rune_page', { pageId, runePage, instance })
 }
@@ -0,0 +1,106 @@
+//! Game data assets served by the LCU, e.g. champion icons and splash art.
+
+use std::path::{Component, Path, PathBuf};
+
+use tokio::fs;
+
+use crate::LcuClient;
+
+/// Path prefix of game data assets.
+pub const ASSET_PREFIX: &str = "/lol-game-data/assets/";
+
+/// A game data asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asset {
+    pub bytes: Vec<u8>,
+    pub content_type: &'static str,
+}
+
+/// Normalize an asset `path` like
+/// `/lol-game-data/assets/v1/champion-icons/1.png` and return the part after
+/// [`ASSET_PREFIX`]. Extra leading slashes are ignored.
+fn relative_path(path: &str) -> crate::Result<&str> {
+    let invalid = || crate::Error::InvalidAssetPath(path.to_owned());
+    let relative = path
+        .trim_start_matches('/')
+        .strip_prefix(&ASSET_PREFIX[1..])
+        .ok_or_else(invalid)?;
+
+    // Keep cached files inside the cache dir.
+    let safe = !relative.is_empty()
+        && Path::new(relative)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if safe { Ok(relative) } else { Err(invalid()) }
+}
+
+/// Content type of an asset from its file extension.
+fn content_type(path: &str) -> &'static str {
+    let extension = path.rsplit_once('.').map_or("", |(_, ext)| ext);
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "webm" => "video/webm",
+        "ogg" => "audio/ogg",
+        "json" => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+impl LcuClient {
+    /// Get a game data asset like
+    /// `/lol-game-data/assets/v1/champion-icons/1.png`. Assets are read
+    /// from and saved to
+    /// [`ClientConfig::asset_cache_dir`](crate::ClientConfig::asset_cache_dir)
+    /// if it's set.
+    ///
+    /// - GET [/lol-game-data/assets/{path}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-game-data/GetLolGameDataAssetsByPath)
+    pub async fn get_asset(&self, path: &str) -> crate::Result<Asset> {
+        let relative = relative_path(path)?;
+        let content_type = content_type(relative);
+        let cached = self
+            .config()
+            .asset_cache_dir
+            .as_ref()
+            .map(|dir| dir.join(relative));
+
+        if let Some(cached) = &cached
+            && let Ok(bytes) = fs::read(cached).await
+        {
+            return Ok(Asset {
+                bytes,
+                content_type,
+            });
+        }
+
+        let bytes = self
+            .get_raw(&format!("{ASSET_PREFIX}{relative}"))
+            .await?
+            .bytes()
+            .await?
+            .to_vec();
+        if let Some(cached) = cached {
+            // Caching is best effort; the asset is still returned.
+            _ = save(&cached, &bytes).await;
+        }
+
+        Ok(Asset {
+            bytes,
+            content_type,
+        })
+    }
+}
+
+/// Write `bytes` to `path` through a temporary file, so partially written
+/// assets are never read from the cache.
+async fn save(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut tmp = PathBuf::from(path);
+    tmp.as_mut_os_string().push(".part");
+    fs::write(&tmp, bytes).await?;
+    fs::rename(&tmp, path).await
+}
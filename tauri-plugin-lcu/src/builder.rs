@@ -0,0 +1,235 @@
+use std::{path::PathBuf, time::Duration};
+
+#[cfg(feature = "tauri-plugin-store")]
+use tauri::AppHandle;
+use tauri::{
+    Manager, Runtime, async_runtime,
+    plugin::{Builder as PluginBuilder, TauriPlugin},
+};
+#[cfg(feature = "tauri-plugin-store")]
+use tauri_plugin_store::{JsonValue, StoreExt};
+use tokio::task;
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+use crate::{ClientConfig, Lcu, LcuClient, LcuState, Locator, RequestPolicy, commands, protocol};
+
+/// Events emitted by the plugin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// `lcu-connected`
+    Connected,
+    /// `lcu-lockfile`
+    LockFile,
+    /// `lcu-base-url`
+    BaseUrl,
+    /// `lcu-instances`
+    Instances,
+}
+
+/// Plugin configuration, managed in [`LcuState`].
+#[derive(Debug, Clone)]
+pub struct Config {
+    #[cfg(feature = "tauri-plugin-store")]
+    pub store_file: Option<String>,
+    #[cfg(feature = "tauri-plugin-store")]
+    pub store_key: String,
+    pub events: Vec<Event>,
+    pub client: ClientConfig,
+}
+
+/// Builder for the LCU plugin.
+#[derive(Debug)]
+pub struct Builder {
+    config: Config,
+}
+
+impl Event {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Connected => "lcu-connected",
+            Self::LockFile => "lcu-lockfile",
+            Self::BaseUrl => "lcu-base-url",
+            Self::Instances => "lcu-instances",
+        }
+    }
+}
+
+impl Config {
+    pub fn emits(&self, event: Event) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self {
+            config: Config {
+                #[cfg(feature = "tauri-plugin-store")]
+                store_file: None,
+                #[cfg(feature = "tauri-plugin-store")]
+                store_key: "lockfile_path".to_owned(),
+                events: vec![
+                    Event::Connected,
+                    Event::LockFile,
+                    Event::BaseUrl,
+                    Event::Instances,
+                ],
+                client: ClientConfig::default(),
+            },
+        }
+    }
+
+    /// Path to a `tauri-plugin-store` store. The LCU lockfile path will be
+    /// saved in the store under [`Self::store_key`]. Nothing is saved if this
+    /// isn't set.
+    #[cfg(feature = "tauri-plugin-store")]
+    pub fn store_file(mut self, store_file: impl ToString) -> Self {
+        self.config.store_file = Some(store_file.to_string());
+        self
+    }
+
+    /// Store key of the lockfile path. Defaults to `lockfile_path`.
+    #[cfg(feature = "tauri-plugin-store")]
+    pub fn store_key(mut self, store_key: impl ToString) -> Self {
+        self.config.store_key = store_key.to_string();
+        self
+    }
+
+    /// How often to look for new lockfiles. Defaults to 5 seconds.
+    pub const fn discovery_interval(mut self, interval: Duration) -> Self {
+        self.config.client.discovery_interval = interval;
+        self
+    }
+
+    /// How often to look for new lockfiles while an instance is connected.
+    /// Defaults to 60 seconds.
+    pub const fn connected_discovery_interval(mut self, interval: Duration) -> Self {
+        self.config.client.connected_discovery_interval = interval;
+        self
+    }
+
+    /// How long to wait for lockfile changes to settle before reading it.
+    /// Defaults to 1 second.
+    pub const fn debounce(mut self, debounce: Duration) -> Self {
+        self.config.client.debounce = debounce;
+        self
+    }
+
+    /// Strategies to find lockfiles; paths from all locators are watched.
+    /// Defaults to
+    /// [`Locator::Store`] (with the `tauri-plugin-store` feature), then
+    /// [`Locator::Process`].
+    pub fn locators(mut self, locators: impl IntoIterator<Item = Locator>) -> Self {
+        self.config.client.locators = locators.into_iter().collect();
+        self
+    }
+
+    /// PEM encoded root certificate used to verify the LCU API. Defaults to
+    /// the Riot Games root certificate.
+    pub fn root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.config.client.root_certificate = pem.into();
+        self
+    }
+
+    pub const fn request_policy(mut self, policy: RequestPolicy) -> Self {
+        self.config.client.request_policy = policy;
+        self
+    }
+
+    /// Dir to cache game data assets served by the `lcu-asset://` URI scheme
+    /// in. Defaults to `lcu-assets` in the app cache dir.
+    pub fn asset_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.client.asset_cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Events to emit. Defaults to all events.
+    pub fn events(mut self, events: impl IntoIterator<Item = Event>) -> Self {
+        self.config.events = events.into_iter().collect();
+        self
+    }
+
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        let config = self.config;
+
+        PluginBuilder::new("lcu")
+            .invoke_handler(tauri::generate_handler![
+                commands::connected,
+                commands::instances,
+                commands::set_active_instance,
+                commands::diagnose,
+                commands::get_summoner_by_riot_id,
+                #[cfg(feature = "ugg-types")]
+                commands::get_current_summoner,
+                #[cfg(feature = "ugg-types")]
+                commands::get_current_rune_page,
+                #[cfg(feature = "ugg-types")]
+                commands::update_rune_page,
+            ])
+            .register_asynchronous_uri_scheme_protocol(protocol::SCHEME, protocol::handle)
+            .setup(|app, _| {
+                let mut client_config = config.client.clone();
+                if client_config.asset_cache_dir.is_none() {
+                    client_config.asset_cache_dir = app
+                        .path()
+                        .app_cache_dir()
+                        .ok()
+                        .map(|dir| dir.join("lcu-assets"));
+                }
+                #[cfg(feature = "tauri-plugin-store")]
+                {
+                    client_config.stored_lockfile = stored_lockfile(app, &config);
+                }
+
+                let lcu = Lcu::<R>::new(LcuClient::new(client_config));
+                let client = lcu.client.clone();
+                app.manage(lcu);
+                app.manage(LcuState {
+                    config,
+                    cancel_token: CancellationToken::new(),
+                    tracker: TaskTracker::new(),
+                });
+
+                LcuState::forward_events(app);
+                async_runtime::spawn(async move {
+                    _ = client.watch().await;
+                });
+
+                Ok(())
+            })
+            .on_drop(|app| {
+                // Cancel all tasks and wait for them to complete.
+                let lcu = app.state::<Lcu<R>>();
+                let state = app.state::<LcuState>();
+                state.cancel_token.cancel();
+                state.tracker.close();
+
+                task::block_in_place(move || {
+                    async_runtime::block_on(async {
+                        lcu.shutdown().await;
+                        state.tracker.wait().await;
+                    });
+                });
+            })
+            .build()
+    }
+}
+
+/// The lockfile path saved in the store, for [`Locator::Store`].
+#[cfg(feature = "tauri-plugin-store")]
+fn stored_lockfile<R: Runtime>(app: &AppHandle<R>, config: &Config) -> Option<PathBuf> {
+    config
+        .store_file
+        .as_ref()
+        .and_then(|store_file| app.store(store_file).ok())
+        .and_then(|store| match store.get(&config.store_key) {
+            Some(JsonValue::String(path)) => Some(PathBuf::from(path)),
+            _ => None,
+        })
+}
@@ -0,0 +1,514 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use notify_debouncer_full::{
+    DebounceEventResult,
+    notify::{EventKind, RecursiveMode},
+};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{RwLock, broadcast, mpsc},
+    time::{self, Instant},
+};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+use crate::{
+    ReplayClient,
+    lockfile::{self, LockFile},
+};
+
+const ROOT_CERT: &[u8] = include_bytes!("./riotgames.pem");
+
+/// A strategy to find LCU lockfiles. Paths from all locators are watched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Locator {
+    /// The lockfile path saved in the store by a previous connection, see
+    /// [`ClientConfig::stored_lockfile`].
+    #[cfg(feature = "tauri-plugin-store")]
+    Store,
+    /// The install dirs of all running League clients. Uses `WMIC.exe` on
+    /// Windows or `ps` on macOS.
+    Process,
+    /// A fixed lockfile path.
+    Path(PathBuf),
+}
+
+/// How requests to the LCU API are sent. Defaults to no timeout and no
+/// retries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestPolicy {
+    /// Timeout for each request, including reading the response body. No
+    /// timeout if `None`.
+    pub timeout: Option<Duration>,
+    /// Number of times a request is retried if it fails to send, e.g. while
+    /// the client is still starting up. Requests that receive an error status
+    /// are not retried.
+    pub retries: u32,
+}
+
+/// [`LcuClient`] configuration.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// How often to look for new lockfiles.
+    pub discovery_interval: Duration,
+    /// How often to look for new lockfiles while an instance is connected.
+    /// Connected instances are watched, so this only finds other instances.
+    pub connected_discovery_interval: Duration,
+    /// How long to wait for lockfile changes to settle before reading it.
+    pub debounce: Duration,
+    /// Strategies to find lockfiles.
+    pub locators: Vec<Locator>,
+    /// PEM encoded root certificate used to verify the LCU API.
+    pub root_certificate: Vec<u8>,
+    pub request_policy: RequestPolicy,
+    /// Dir to cache game data assets in, see [`LcuClient::get_asset`]. Assets
+    /// aren't cached if `None`.
+    pub asset_cache_dir: Option<PathBuf>,
+    /// Lockfile path found by [`Locator::Store`]. The Tauri plugin sets it
+    /// from its store.
+    #[cfg(feature = "tauri-plugin-store")]
+    pub stored_lockfile: Option<PathBuf>,
+}
+
+/// Identifies a League client instance, e.g. live or PBE, by its install dir.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InstanceId(PathBuf);
+
+/// A connected League client instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Instance {
+    pub id: InstanceId,
+    pub lockfile: LockFile,
+    pub base_url: Url,
+    /// Whether requests go to this instance by default.
+    pub active: bool,
+}
+
+/// Connection changes, see [`LcuClient::subscribe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LcuEvent {
+    /// Connected to an instance, or reconnected after its lockfile changed.
+    Connected {
+        instance: InstanceId,
+        lockfile: LockFile,
+        base_url: Url,
+    },
+    /// An instance's lockfile was removed, i.e. the League client closed.
+    Disconnected { instance: InstanceId },
+    /// The active instance changed.
+    ActiveChanged { instance: Option<InstanceId> },
+}
+
+/// LCU API client that doesn't depend on Tauri.
+///
+/// The client starts disconnected. Either [`connect`](Self::connect) with a
+/// [`LockFile`], or run [`watch`](Self::watch) in a background task to find
+/// lockfiles and keep the connections up to date. Cloning is cheap and clones
+/// share the same connections.
+///
+/// Several League clients can be connected at once. Requests go to the active
+/// instance unless the client is scoped to another one with
+/// [`instance`](Self::instance).
+#[derive(Debug, Clone)]
+pub struct LcuClient {
+    inner: Arc<Inner>,
+    /// Instance to send requests to instead of the active one.
+    target: Option<InstanceId>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    config: ClientConfig,
+    instances: RwLock<Instances>,
+    events: broadcast::Sender<LcuEvent>,
+    /// Used to cancel [`LcuClient::watch`].
+    cancel_token: CancellationToken,
+    /// Used to wait for [`LcuClient::watch`] to complete.
+    tracker: TaskTracker,
+    /// Built on first use, see [`LcuClient::replay`].
+    replay: OnceLock<ReplayClient>,
+}
+
+#[derive(Debug, Default)]
+struct Instances {
+    connections: BTreeMap<InstanceId, Connection>,
+    active: Option<InstanceId>,
+}
+
+#[derive(Debug)]
+struct Connection {
+    lockfile: LockFile,
+    /// LCU API base URL, including protocol, hostname, and port.
+    base_url: Url,
+    /// HTTP client with auth from the lockfile.
+    client: Client,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            discovery_interval: Duration::from_secs(5),
+            connected_discovery_interval: Duration::from_secs(60),
+            debounce: Duration::from_secs(1),
+            locators: vec![
+                #[cfg(feature = "tauri-plugin-store")]
+                Locator::Store,
+                Locator::Process,
+            ],
+            root_certificate: ROOT_CERT.to_vec(),
+            request_policy: RequestPolicy::default(),
+            asset_cache_dir: None,
+            #[cfg(feature = "tauri-plugin-store")]
+            stored_lockfile: None,
+        }
+    }
+}
+
+impl InstanceId {
+    /// The instance of the lockfile at `lockfile_path`.
+    pub fn of(lockfile_path: &Path) -> Self {
+        Self(lockfile_path.parent().unwrap_or(lockfile_path).to_owned())
+    }
+
+    pub fn install_dir(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl fmt::Display for InstanceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.display().fmt(f)
+    }
+}
+
+impl Instances {
+    /// The target instance, or the active instance if `target` is `None`.
+    fn get(&self, target: Option<&InstanceId>) -> Option<&Connection> {
+        self.connections.get(target.or(self.active.as_ref())?)
+    }
+
+    /// Remove the target instance, or the active instance if `target` is
+    /// `None`. Returns the removed instance, and the new active instance if
+    /// it changed.
+    fn remove(
+        &mut self,
+        target: Option<&InstanceId>,
+    ) -> Option<(InstanceId, Option<Option<InstanceId>>)> {
+        let instance = target.or(self.active.as_ref())?.clone();
+        self.connections.remove(&instance)?;
+        let active = (self.active.as_ref() == Some(&instance)).then(|| {
+            self.active = self.connections.keys().next().cloned();
+            self.active.clone()
+        });
+
+        Some((instance, active))
+    }
+}
+
+impl Default for LcuClient {
+    fn default() -> Self {
+        Self::new(ClientConfig::default())
+    }
+}
+
+impl LcuClient {
+    pub fn new(config: ClientConfig) -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                instances: RwLock::new(Instances::default()),
+                events,
+                cancel_token: CancellationToken::new(),
+                tracker: TaskTracker::new(),
+                replay: OnceLock::new(),
+            }),
+            target: None,
+        }
+    }
+
+    pub fn config(&self) -> &ClientConfig {
+        &self.inner.config
+    }
+
+    /// A replay API client with this client's root certificate and request
+    /// policy. It's built once and shared by all clones.
+    pub fn replay(&self) -> crate::Result<ReplayClient> {
+        if let Some(replay) = self.inner.replay.get() {
+            return Ok(replay.clone());
+        }
+        let replay = ReplayClient::new(self.config())?;
+        Ok(self.inner.replay.get_or_init(|| replay).clone())
+    }
+
+    /// A clone of this client that sends requests to `instance` instead of
+    /// the active instance.
+    pub fn instance(&self, instance: InstanceId) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            target: Some(instance),
+        }
+    }
+
+    /// Subscribe to connection changes.
+    pub fn subscribe(&self) -> broadcast::Receiver<LcuEvent> {
+        self.inner.events.subscribe()
+    }
+
+    /// Connect to the instance of `lockfile`, replacing its current
+    /// connection. The instance becomes active if there's no active instance.
+    pub async fn connect(&self, lockfile: LockFile) -> crate::Result<()> {
+        let client = crate::http::client(&lockfile, &self.inner.config)?;
+        let base_url = lockfile.base_url()?;
+        let instance = lockfile.instance_id();
+        let activated = {
+            let mut lock = self.inner.instances.write().await;
+            lock.connections.insert(
+                instance.clone(),
+                Connection {
+                    lockfile: lockfile.clone(),
+                    base_url: base_url.clone(),
+                    client,
+                },
+            );
+            let activated = lock.active.is_none();
+            if activated {
+                lock.active = Some(instance.clone());
+            }
+            activated
+        };
+        self.emit(LcuEvent::Connected {
+            instance: instance.clone(),
+            lockfile,
+            base_url,
+        });
+        if activated {
+            self.emit(LcuEvent::ActiveChanged {
+                instance: Some(instance),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Drop the connection to the target instance, if any. If it was active,
+    /// another connected instance becomes active.
+    pub async fn disconnect(&self) {
+        let removed = self
+            .inner
+            .instances
+            .write()
+            .await
+            .remove(self.target.as_ref());
+        let Some((instance, active)) = removed else {
+            return;
+        };
+        self.emit(LcuEvent::Disconnected { instance });
+        if let Some(instance) = active {
+            self.emit(LcuEvent::ActiveChanged { instance });
+        }
+    }
+
+    /// Check if the target instance is connected.
+    pub async fn connected(&self) -> bool {
+        let lock = self.inner.instances.read().await;
+        lock.get(self.target.as_ref()).is_some()
+    }
+
+    /// The lockfile of the target instance.
+    pub async fn lockfile(&self) -> Option<LockFile> {
+        let lock = self.inner.instances.read().await;
+        lock.get(self.target.as_ref())
+            .map(|conn| conn.lockfile.clone())
+    }
+
+    /// The LCU API base URL of the target instance.
+    pub async fn base_url(&self) -> Option<Url> {
+        let lock = self.inner.instances.read().await;
+        lock.get(self.target.as_ref())
+            .map(|conn| conn.base_url.clone())
+    }
+
+    /// All connected instances.
+    pub async fn instances(&self) -> Vec<Instance> {
+        let lock = self.inner.instances.read().await;
+        lock.connections
+            .iter()
+            .map(|(id, conn)| Instance {
+                id: id.clone(),
+                lockfile: conn.lockfile.clone(),
+                base_url: conn.base_url.clone(),
+                active: lock.active.as_ref() == Some(id),
+            })
+            .collect()
+    }
+
+    /// The instance requests go to by default.
+    pub async fn active_instance(&self) -> Option<InstanceId> {
+        self.inner.instances.read().await.active.clone()
+    }
+
+    /// Send requests to `instance` by default.
+    pub async fn set_active_instance(&self, instance: InstanceId) -> crate::Result<()> {
+        {
+            let mut lock = self.inner.instances.write().await;
+            if !lock.connections.contains_key(&instance) {
+                return Err(crate::Error::UnknownInstance(instance));
+            }
+            if lock.active.as_ref() == Some(&instance) {
+                return Ok(());
+            }
+            lock.active = Some(instance.clone());
+        }
+        self.emit(LcuEvent::ActiveChanged {
+            instance: Some(instance),
+        });
+
+        Ok(())
+    }
+
+    /// Returns the HTTP client and base URL of the target instance.
+    pub(crate) async fn http(&self) -> crate::Result<(Client, Url)> {
+        let lock = self.inner.instances.read().await;
+        lock.get(self.target.as_ref())
+            .map_or(Err(crate::Error::Disconnected), |conn| {
+                Ok((conn.client.clone(), conn.base_url.clone()))
+            })
+    }
+
+    /// Send `event` to subscribers, if any.
+    fn emit(&self, event: LcuEvent) {
+        _ = self.inner.events.send(event);
+    }
+
+    /// Lockfile paths found by all configured locators.
+    pub async fn locate(&self) -> BTreeSet<PathBuf> {
+        let mut paths = BTreeSet::new();
+        for locator in &self.inner.config.locators {
+            match locator {
+                #[cfg(feature = "tauri-plugin-store")]
+                Locator::Store => paths.extend(self.inner.config.stored_lockfile.clone()),
+                Locator::Process => {
+                    paths.extend(lockfile::process_paths().await.unwrap_or_default());
+                }
+                Locator::Path(path) => {
+                    paths.insert(path.clone());
+                }
+            }
+        }
+
+        paths
+    }
+
+    /// Find lockfiles, then connect and reconnect/disconnect instances as
+    /// their lockfiles change. Runs until [`shutdown`](Self::shutdown) is
+    /// called, so it should be spawned as a background task.
+    pub async fn watch(&self) -> crate::Result<()> {
+        let inner = &self.inner;
+        let cancel_token = inner.cancel_token.clone();
+
+        inner
+            .tracker
+            .track_future(async move {
+                // Watch install dirs rather than lockfiles, since a lockfile
+                // only exists while its client is open. The debounced watcher
+                // makes sure the lockfile isn't read until it has contents.
+                let (tx, mut rx) = mpsc::channel(8);
+                let mut watcher = notify_debouncer_full::new_debouncer(
+                    inner.config.debounce,
+                    None,
+                    move |res: DebounceEventResult| {
+                        let Ok(events) = res else { return };
+                        let paths = events
+                            .iter()
+                            .filter(|evt| {
+                                matches!(
+                                    evt.kind,
+                                    EventKind::Create(_)
+                                        | EventKind::Modify(_)
+                                        | EventKind::Remove(_)
+                                )
+                            })
+                            .flat_map(|evt| &evt.paths)
+                            .filter(|path| path.file_name().is_some_and(|name| name == "lockfile"))
+                            .collect::<BTreeSet<_>>();
+                        for path in paths {
+                            _ = tx.blocking_send((path.clone(), LockFile::read(path).ok()));
+                        }
+                    },
+                )?;
+                let mut watched = BTreeSet::new();
+                let mut interval = time::interval(inner.config.discovery_interval);
+                let mut last_scan = None::<Instant>;
+
+                loop {
+                    tokio::select! {
+                        biased;
+                        () = cancel_token.cancelled() => break,
+                        // Look for new instances every discovery interval.
+                        _ = interval.tick() => {
+                            // Locating spawns a process, so back off while
+                            // connected.
+                            let connected = !inner.instances.read().await.connections.is_empty();
+                            if connected
+                                && last_scan.is_some_and(|last| {
+                                    last.elapsed() < inner.config.connected_discovery_interval
+                                })
+                            {
+                                continue;
+                            }
+                            last_scan = Some(Instant::now());
+
+                            for path in self.locate().await {
+                                let Some(dir) = path.parent() else { continue };
+                                if watched.contains(dir)
+                                    || watcher.watch(dir, RecursiveMode::NonRecursive).is_err()
+                                {
+                                    continue;
+                                }
+                                watched.insert(dir.to_owned());
+                                if let Ok(lockfile) = LockFile::read(&path) {
+                                    _ = self.connect(lockfile).await;
+                                }
+                            }
+                        }
+                        // Reconnect/disconnect when a lockfile is
+                        // created/deleted.
+                        Some((path, msg)) = rx.recv() => {
+                            let client = self.instance(InstanceId::of(&path));
+                            match msg {
+                                Some(lockfile) => {
+                                    if client.lockfile().await.as_ref() != Some(&lockfile) {
+                                        _ = client.connect(lockfile).await;
+                                    }
+                                }
+                                None => client.disconnect().await,
+                            }
+                        }
+                    }
+                }
+
+                for dir in &watched {
+                    _ = watcher.unwatch(dir);
+                }
+
+                Ok(())
+            })
+            .await
+    }
+
+    /// Stop [`watch`](Self::watch) and wait for it to complete.
+    pub async fn shutdown(&self) {
+        self.inner.cancel_token.cancel();
+        self.inner.tracker.close();
+        self.inner.tracker.wait().await;
+    }
+}
@@ -5,12 +5,55 @@ use ugg_types::{
     client_summoner::ClientSummoner,
 };
 
-use crate::LcuExt;
+use crate::{Diagnostics, Instance, InstanceId, LcuClient, LcuExt, models::summoner::Summoner};
+
+/// The client scoped to `instance`, or the active instance if `None`.
+fn lcu<R: Runtime>(app: &AppHandle<R>, instance: Option<InstanceId>) -> LcuClient {
+    instance.map_or_else(
+        || LcuClient::clone(app.lcu()),
+        |instance| app.lcu().instance(instance),
+    )
+}
 
 /// Check if the plugin is connected to the LCU API.
 #[tauri::command]
-pub async fn connected<R: Runtime>(app: AppHandle<R>) -> bool {
-    app.lcu().connected().await
+pub async fn connected<R: Runtime>(app: AppHandle<R>, instance: Option<InstanceId>) -> bool {
+    lcu(&app, instance).connected().await
+}
+
+/// Get all connected League client instances.
+#[tauri::command]
+pub async fn instances<R: Runtime>(app: AppHandle<R>) -> Vec<Instance> {
+    app.lcu().instances().await
+}
+
+/// Send requests to `instance` by default.
+#[tauri::command]
+pub async fn set_active_instance<R: Runtime>(
+    app: AppHandle<R>,
+    instance: InstanceId,
+) -> crate::Result<()> {
+    app.lcu().set_active_instance(instance).await
+}
+
+/// Run every step of finding and connecting to the LCU and report the result
+/// of each.
+#[tauri::command]
+pub async fn diagnose<R: Runtime>(app: AppHandle<R>) -> Diagnostics {
+    app.lcu().diagnose().await
+}
+
+/// Get a summoner by their Riot ID, formatted as `name#tag`.
+///
+/// - GET [/lol-summoner/v1/alias/lookup](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-summoner/GetLolSummonerV1AliasLookup)
+/// - GET [/lol-summoner/v2/summoners/puuid/{puuid}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-summoner/GetLolSummonerV2SummonersPuuidByPuuid)
+#[tauri::command]
+pub async fn get_summoner_by_riot_id<R: Runtime>(
+    app: AppHandle<R>,
+    instance: Option<InstanceId>,
+    riot_id: &str,
+) -> crate::Result<Summoner> {
+    lcu(&app, instance).get_summoner_by_riot_id(riot_id).await
 }
 
 /// Get the current summoner.
@@ -18,8 +61,11 @@ pub async fn connected<R: Runtime>(app: AppHandle<R>) -> bool {
 /// - GET [/lol-summoner/v1/current-summoner](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-summoner/GetLolSummonerV1CurrentSummoner)
 #[cfg(feature = "ugg-types")]
 #[tauri::command]
-pub async fn get_current_summoner<R: Runtime>(app: AppHandle<R>) -> crate::Result<ClientSummoner> {
-    app.lcu().get_current_summoner().await
+pub async fn get_current_summoner<R: Runtime>(
+    app: AppHandle<R>,
+    instance: Option<InstanceId>,
+) -> crate::Result<ClientSummoner> {
+    lcu(&app, instance).get_current_summoner().await
 }
 
 /// Get the current rune page, unless `prefix` is provided. In the latter case,
@@ -30,9 +76,10 @@ pub async fn get_current_summoner<R: Runtime>(app: AppHandle<R>) -> crate::Resul
 #[tauri::command]
 pub async fn get_current_rune_page<R: Runtime>(
     app: AppHandle<R>,
+    instance: Option<InstanceId>,
     prefix: Option<&str>,
 ) -> crate::Result<RunePage> {
-    app.lcu().get_current_rune_page(prefix).await
+    lcu(&app, instance).get_current_rune_page(prefix).await
 }
 
 /// Deletes the rune page with `page_id`, and adds `rune_page`.
@@ -43,9 +90,12 @@ pub async fn get_current_rune_page<R: Runtime>(
 #[tauri::command]
 pub async fn update_rune_page<R: Runtime>(
     app: AppHandle<R>,
+    instance: Option<InstanceId>,
     page_id: i64,
     rune_page: NewRunePage,
 ) -> crate::Result<()> {
-    app.lcu().update_rune_page(page_id, &rune_page).await?;
+    lcu(&app, instance)
+        .update_rune_page(page_id, &rune_page)
+        .await?;
     Ok(())
 }
@@ -0,0 +1,251 @@
+//! Step by step diagnostics of the LCU connection, for when it isn't
+//! connecting.
+
+use std::{
+    collections::BTreeSet, error::Error as StdError, net::Ipv4Addr, path::PathBuf, time::Duration,
+};
+
+use reqwest::{Certificate, ClientBuilder};
+use serde::Serialize;
+use tokio::{net::TcpStream, time};
+
+use crate::{LcuClient, Locator, LockFile, http, lockfile};
+
+/// Timeout of each network step.
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// Path of the authenticated test request.
+const TEST_PATH: &str = "/riotclient/region-locale";
+
+/// Result of a diagnostic step.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum Check<T> {
+    Passed {
+        value: T,
+    },
+    Failed {
+        error: String,
+    },
+    /// Not run because a step it depends on failed.
+    Skipped,
+}
+
+/// Diagnostics of every step of connecting to the LCU, see
+/// [`LcuClient::diagnose`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostics {
+    pub locators: Vec<LocatorDiagnostics>,
+    /// One per lockfile path found by the locators.
+    pub lockfiles: Vec<LockFileDiagnostics>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocatorDiagnostics {
+    /// `store`, `process`, or `path`.
+    pub locator: &'static str,
+    /// Lockfile paths found by the locator.
+    pub paths: Check<Vec<PathBuf>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockFileDiagnostics {
+    pub path: PathBuf,
+    /// Whether the client is connected to this lockfile's instance.
+    pub connected: bool,
+    /// Parsing the lockfile. The auth token is left out so diagnostics can be
+    /// shared.
+    pub lockfile: Check<ParsedLockFile>,
+    /// Whether the process that wrote the lockfile is running.
+    pub process: Check<()>,
+    /// Opening a TCP connection to the port.
+    pub tcp: Check<()>,
+    /// TLS handshake against the root certificate, with the status of an
+    /// unauthenticated request.
+    pub tls: Check<u16>,
+    /// Status of an authenticated request.
+    pub request: Check<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedLockFile {
+    pub pid: u32,
+    pub port: u16,
+}
+
+impl<T> Check<T> {
+    fn from_result<E: StdError>(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => Self::Passed { value },
+            Err(err) => Self::failed(&err),
+        }
+    }
+
+    /// A failed check with the error and all of its sources.
+    fn failed(err: &dyn StdError) -> Self {
+        let mut error = err.to_string();
+        let mut source = err.source();
+        while let Some(err) = source {
+            error.push_str(": ");
+            error.push_str(&err.to_string());
+            source = err.source();
+        }
+
+        Self::Failed { error }
+    }
+
+    const fn passed(&self) -> bool {
+        matches!(self, Self::Passed { .. })
+    }
+}
+
+impl LcuClient {
+    /// Run every step of finding and connecting to the LCU and report the
+    /// result of each. Unlike [`watch`](Self::watch), nothing is retried;
+    /// steps that depend on a failed step are skipped.
+    pub async fn diagnose(&self) -> Diagnostics {
+        let mut locators = Vec::new();
+        let mut paths = BTreeSet::new();
+        for locator in &self.config().locators {
+            let (name, check) = match locator {
+                #[cfg(feature = "tauri-plugin-store")]
+                Locator::Store => (
+                    "store",
+                    Check::Passed {
+                        value: self.config().stored_lockfile.iter().cloned().collect(),
+                    },
+                ),
+                Locator::Process => (
+                    "process",
+                    Check::from_result(lockfile::process_paths().await),
+                ),
+                Locator::Path(path) => (
+                    "path",
+                    Check::Passed {
+                        value: vec![path.clone()],
+                    },
+                ),
+            };
+            if let Check::Passed { value } = &check {
+                paths.extend(value.iter().cloned());
+            }
+            locators.push(LocatorDiagnostics {
+                locator: name,
+                paths: check,
+            });
+        }
+
+        let connected = self
+            .instances()
+            .await
+            .into_iter()
+            .map(|instance| instance.lockfile.path)
+            .collect::<BTreeSet<_>>();
+        let mut lockfiles = Vec::with_capacity(paths.len());
+        for path in paths {
+            let mut diagnostics = self.diagnose_lockfile(path).await;
+            diagnostics.connected = connected.contains(&diagnostics.path);
+            lockfiles.push(diagnostics);
+        }
+
+        Diagnostics {
+            locators,
+            lockfiles,
+        }
+    }
+
+    async fn diagnose_lockfile(&self, path: PathBuf) -> LockFileDiagnostics {
+        let mut diagnostics = LockFileDiagnostics {
+            path,
+            connected: false,
+            lockfile: Check::Skipped,
+            process: Check::Skipped,
+            tcp: Check::Skipped,
+            tls: Check::Skipped,
+            request: Check::Skipped,
+        };
+
+        let lockfile = match LockFile::read(&diagnostics.path) {
+            Ok(lockfile) => lockfile,
+            Err(err) => {
+                diagnostics.lockfile = Check::failed(&err);
+                return diagnostics;
+            }
+        };
+        diagnostics.lockfile = Check::Passed {
+            value: ParsedLockFile {
+                pid: lockfile.pid,
+                port: lockfile.port,
+            },
+        };
+
+        diagnostics.process = match lockfile::process_alive(lockfile.pid).await {
+            Ok(true) => Check::Passed { value: () },
+            Ok(false) => Check::Failed {
+                error: format!("process {} is not running", lockfile.pid),
+            },
+            Err(err) => Check::failed(&err),
+        };
+
+        diagnostics.tcp = match time::timeout(
+            TIMEOUT,
+            TcpStream::connect((Ipv4Addr::LOCALHOST, lockfile.port)),
+        )
+        .await
+        {
+            Ok(result) => Check::from_result(result.map(drop)),
+            Err(err) => Check::failed(&err),
+        };
+        if !diagnostics.tcp.passed() {
+            return diagnostics;
+        }
+
+        let base_url = match lockfile.base_url() {
+            Ok(base_url) => base_url,
+            Err(err) => {
+                diagnostics.tls = Check::failed(&err);
+                return diagnostics;
+            }
+        };
+
+        // Any response, even an auth error, means the handshake succeeded.
+        let tls = async {
+            let client = ClientBuilder::new()
+                .https_only(true)
+                .tls_built_in_root_certs(false)
+                .add_root_certificate(Certificate::from_pem(&self.config().root_certificate)?)
+                .timeout(TIMEOUT)
+                .build()?;
+            let res = client.get(base_url.as_str()).send().await?;
+            crate::Result::Ok(res.status().as_u16())
+        };
+        diagnostics.tls = Check::from_result(tls.await);
+        if !diagnostics.tls.passed() {
+            return diagnostics;
+        }
+
+        let request = async {
+            let client = http::client(&lockfile, self.config())?;
+            let res = client
+                .get(base_url.join(TEST_PATH)?)
+                .timeout(TIMEOUT)
+                .send()
+                .await?;
+            let status = res.status();
+            if status.is_success() {
+                Ok(status.as_u16())
+            } else {
+                Err(crate::Error::StatusCode {
+                    status,
+                    text: res.text().await?,
+                })
+            }
+        };
+        diagnostics.request = Check::from_result(request.await);
+
+        diagnostics
+    }
+}
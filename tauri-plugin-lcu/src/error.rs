@@ -1,5 +1,8 @@
+use notify_debouncer_full::notify;
+use reqwest::StatusCode;
 use serde::{Serialize, ser::Serializer};
-use tauri_plugin_http::reqwest::StatusCode;
+
+use crate::InstanceId;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -9,26 +12,38 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     Utf8(#[from] std::str::Utf8Error),
-    #[error(transparent)]
-    Shell(#[from] tauri_plugin_shell::Error),
     #[error("command output status code {0:?}")]
     Command(Option<i32>),
     #[error("parsing command output")]
     ParseCommand,
+    #[error("parsing lockfile")]
+    ParseLockFile,
+    #[error(transparent)]
+    Notify(#[from] notify::Error),
+    #[cfg(feature = "tauri")]
     #[error(transparent)]
     Tauri(#[from] tauri::Error),
+    #[cfg(feature = "tauri-plugin-store")]
     #[error(transparent)]
     Store(#[from] tauri_plugin_store::Error),
     #[error(transparent)]
     UrlParse(#[from] url::ParseError),
     #[error(transparent)]
-    Reqwest(#[from] tauri_plugin_http::reqwest::Error),
+    Reqwest(#[from] reqwest::Error),
     #[error(transparent)]
-    InvalidHeaderValue(#[from] tauri_plugin_http::reqwest::header::InvalidHeaderValue),
+    InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
     #[error("request failed with status {status}: {text}")]
     StatusCode { status: StatusCode, text: String },
     #[error("not connected to the LCU")]
     Disconnected,
+    #[error("not connected to the League client instance {0}")]
+    UnknownInstance(InstanceId),
+    #[error("invalid game data asset path {0:?}")]
+    InvalidAssetPath(String),
+    #[error("invalid Riot ID {0:?}, expected `name#tag`")]
+    InvalidRiotId(String),
+    #[error("no summoner with Riot ID {0:?}")]
+    SummonerNotFound(String),
     #[error("{0}")]
     Custom(&'static str),
 }
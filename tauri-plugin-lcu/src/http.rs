@@ -1,24 +1,37 @@
-use serde::{Serialize, de::DeserializeOwned};
-use tauri::{Manager, Runtime};
-use tauri_plugin_http::reqwest::{
-    Certificate, Client, ClientBuilder, Method, Response, Url,
+use reqwest::{
+    Certificate, Client, ClientBuilder, Method, RequestBuilder, Response,
     header::{self, HeaderMap, HeaderValue},
 };
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
 #[cfg(feature = "ugg-types")]
 use ugg_types::{
     client_runepage::{NewRunePage, RunePage, RunePages},
     client_summoner::ClientSummoner,
 };
+use url::form_urlencoded;
 
-use crate::{Lcu, LcuState, lockfile::LockFile};
-
-const ROOT_CERT: &[u8] = include_bytes!("./riotgames.pem");
+use crate::{
+    ClientConfig, LcuClient, LockFile,
+    models::{
+        champ_select::ChampSelectSession,
+        champions::ChampionMinimal,
+        chat::{ChatMe, Friend},
+        gameflow::GameflowSession,
+        loot::{LootItem, LootRecipe},
+        mastery::ChampionMastery,
+        match_history::{GameTimeline, MatchHistory, MatchHistoryGame},
+        ranked::RankedStats,
+        riotclient::RegionLocale,
+        summoner::{AliasLookup, Summoner},
+    },
+};
 
-trait ResultExt<T> {
+pub trait ResultExt<T> {
     async fn check_status(self) -> crate::Result<T>;
 }
 
-impl ResultExt<Response> for tauri_plugin_http::reqwest::Result<Response> {
+impl ResultExt<Response> for reqwest::Result<Response> {
     async fn check_status(self) -> crate::Result<Response> {
         match self {
             Ok(res) if res.status().is_success() => Ok(res),
@@ -32,48 +45,46 @@ impl ResultExt<Response> for tauri_plugin_http::reqwest::Result<Response> {
 }
 
 /// Build a new HTTP client with auth from a lockfile.
-pub fn client(lockfile: &LockFile) -> crate::Result<Client> {
+pub fn client(lockfile: &LockFile, config: &ClientConfig) -> crate::Result<Client> {
     let headers = HeaderMap::from_iter([(
         header::AUTHORIZATION,
         HeaderValue::from_str(&lockfile.auth_header)?,
     )]);
-
-    Ok(ClientBuilder::new()
+    let mut builder = ClientBuilder::new()
         .https_only(true)
         .tls_built_in_root_certs(false)
-        .add_root_certificate(Certificate::from_pem(ROOT_CERT)?)
-        .default_headers(headers)
-        .build()?)
+        .add_root_certificate(Certificate::from_pem(&config.root_certificate)?)
+        .default_headers(headers);
+    if let Some(timeout) = config.request_policy.timeout {
+        builder = builder.timeout(timeout);
+    }
+
+    Ok(builder.build()?)
 }
 
-impl<R: Runtime> Lcu<R> {
-    /// Returns the HTTP client if available.
-    async fn client(&self) -> crate::Result<Client> {
-        let state = self.0.state::<LcuState>();
-        let lock = state.client.read().await;
-        lock.as_ref()
-            .map_or(Err(crate::Error::Disconnected), |client| Ok(client.clone()))
-    }
-
-    /// Add a path to the LCU API base URL.
-    async fn url(&self, path: &str) -> crate::Result<Url> {
-        let state = self.0.state::<LcuState>();
-        let lock = state.base_url.read().await;
-        match &*lock {
-            Some(url) => Ok(url.join(path)?),
-            None => Err(crate::Error::Disconnected),
+/// Send a request, retrying up to `retries` times if it fails to send.
+pub async fn send(request: RequestBuilder, mut retries: u32) -> crate::Result<Response> {
+    loop {
+        // Requests with a streaming body can't be cloned, but all requests
+        // built here have either no body or a JSON body.
+        let Some(attempt) = request.try_clone() else {
+            return request.send().await.check_status().await;
+        };
+        match attempt.send().await {
+            Err(err) if retries > 0 && (err.is_connect() || err.is_timeout()) => {
+                retries -= 1;
+            }
+            res => return res.check_status().await,
         }
     }
+}
 
+impl LcuClient {
     /// Send a request to the LCU API.
     async fn request(&self, method: Method, path: &str) -> crate::Result<Response> {
-        self.client()
-            .await?
-            .request(method, self.url(path).await?)
-            .send()
-            .await
-            .check_status()
-            .await
+        let (client, base_url) = self.http().await?;
+        let retries = self.config().request_policy.retries;
+        send(client.request(method, base_url.join(path)?), retries).await
     }
 
     /// Send a request to the LCU API with a JSON body.
@@ -83,21 +94,13 @@ impl<R: Runtime> Lcu<R> {
         path: &str,
         body: &T,
     ) -> crate::Result<Response> {
-        self.client()
-            .await?
-            .request(method, self.url(path).await?)
-            .json(&body)
-            .send()
-            .await
-            .check_status()
-            .await
-    }
-
-    /// Check if the plugin is connected to the LCU API.
-    pub async fn connected(&self) -> bool {
-        let state = self.0.state::<LcuState>();
-        let lock = state.client.read().await;
-        lock.is_some()
+        let (client, base_url) = self.http().await?;
+        let retries = self.config().request_policy.retries;
+        send(
+            client.request(method, base_url.join(path)?).json(&body),
+            retries,
+        )
+        .await
     }
 
     /// Send a GET request and deserialize the response body as JSON.
@@ -199,4 +202,254 @@ impl<R: Runtime> Lcu<R> {
             .await?;
         self.post("/lol-perks/v1/pages", rune_page).await
     }
+
+    /// Get the current champ select session.
+    ///
+    /// - GET [/lol-champ-select/v1/session](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champ-select/GetLolChampSelectV1Session)
+    pub async fn get_champ_select_session(&self) -> crate::Result<ChampSelectSession> {
+        self.get("/lol-champ-select/v1/session").await
+    }
+
+    /// Swap the local player's champion with `champion_id` from the bench.
+    ///
+    /// - POST [/lol-champ-select/v1/session/bench/swap/{champion_id}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champ-select/PostLolChampSelectV1SessionBenchSwapByChampionId)
+    pub async fn bench_swap(&self, champion_id: i64) -> crate::Result<Response> {
+        self.request(
+            Method::POST,
+            &format!("/lol-champ-select/v1/session/bench/swap/{champion_id}"),
+        )
+        .await
+    }
+
+    /// Get the IDs of champions the local player can pick in the current champ
+    /// select.
+    ///
+    /// - GET [/lol-champ-select/v1/pickable-champion-ids](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champ-select/GetLolChampSelectV1PickableChampionIds)
+    pub async fn get_pickable_champion_ids(&self) -> crate::Result<Vec<i64>> {
+        self.get("/lol-champ-select/v1/pickable-champion-ids").await
+    }
+
+    /// Get the IDs of champions the local player can ban in the current champ
+    /// select.
+    ///
+    /// - GET [/lol-champ-select/v1/bannable-champion-ids](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champ-select/GetLolChampSelectV1BannableChampionIds)
+    pub async fn get_bannable_champion_ids(&self) -> crate::Result<Vec<i64>> {
+        self.get("/lol-champ-select/v1/bannable-champion-ids").await
+    }
+
+    /// Hover `champion_id` for the pick or ban action with `action_id`, then
+    /// lock it in if `complete` is set.
+    ///
+    /// - PATCH [/lol-champ-select/v1/session/actions/{action_id}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champ-select/PatchLolChampSelectV1SessionActionsById)
+    /// - POST [/lol-champ-select/v1/session/actions/{action_id}/complete](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champ-select/PostLolChampSelectV1SessionActionsByIdComplete)
+    pub async fn select_champion(
+        &self,
+        action_id: i64,
+        champion_id: i64,
+        complete: bool,
+    ) -> crate::Result<Response> {
+        let path = format!("/lol-champ-select/v1/session/actions/{action_id}");
+        let res = self
+            .patch(&path, &json!({ "championId": champion_id }))
+            .await?;
+        if !complete {
+            return Ok(res);
+        }
+
+        self.request(Method::POST, &format!("{path}/complete"))
+            .await
+    }
+
+    /// Get all champions the current summoner owns, rents, or can play for
+    /// free.
+    ///
+    /// - GET [/lol-champions/v1/owned-champions-minimal](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champions/GetLolChampionsV1OwnedChampionsMinimal)
+    pub async fn get_owned_champions(&self) -> crate::Result<Vec<ChampionMinimal>> {
+        self.get("/lol-champions/v1/owned-champions-minimal").await
+    }
+
+    /// Get the in-game settings (graphics, sound, interface, etc.).
+    ///
+    /// - GET [/lol-game-settings/v1/game-settings](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-game-settings/GetLolGameSettingsV1GameSettings)
+    pub async fn get_game_settings(&self) -> crate::Result<Value> {
+        self.get("/lol-game-settings/v1/game-settings").await
+    }
+
+    /// Update the in-game settings with `settings` and save them to disk.
+    ///
+    /// - PATCH [/lol-game-settings/v1/game-settings](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-game-settings/PatchLolGameSettingsV1GameSettings)
+    /// - POST [/lol-game-settings/v1/save](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-game-settings/PostLolGameSettingsV1Save)
+    pub async fn patch_game_settings(&self, settings: &Value) -> crate::Result<Response> {
+        self.patch("/lol-game-settings/v1/game-settings", settings)
+            .await?;
+        self.request(Method::POST, "/lol-game-settings/v1/save")
+            .await
+    }
+
+    /// Get the in-game input settings (keybinds).
+    ///
+    /// - GET [/lol-game-settings/v1/input-settings](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-game-settings/GetLolGameSettingsV1InputSettings)
+    pub async fn get_input_settings(&self) -> crate::Result<Value> {
+        self.get("/lol-game-settings/v1/input-settings").await
+    }
+
+    /// Update the in-game input settings with `settings` and save them to
+    /// disk.
+    ///
+    /// - PATCH [/lol-game-settings/v1/input-settings](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-game-settings/PatchLolGameSettingsV1InputSettings)
+    /// - POST [/lol-game-settings/v1/save](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-game-settings/PostLolGameSettingsV1Save)
+    pub async fn patch_input_settings(&self, settings: &Value) -> crate::Result<Response> {
+        self.patch("/lol-game-settings/v1/input-settings", settings)
+            .await?;
+        self.request(Method::POST, "/lol-game-settings/v1/save")
+            .await
+    }
+
+    /// Get the current summoner's champion mastery for all played champions.
+    ///
+    /// - GET [/lol-champion-mastery/v1/local-player/champion-mastery](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champion-mastery/GetLolChampionMasteryV1LocalPlayerChampionMastery)
+    pub async fn get_champion_mastery(&self) -> crate::Result<Vec<ChampionMastery>> {
+        self.get("/lol-champion-mastery/v1/local-player/champion-mastery")
+            .await
+    }
+
+    /// Get the champion mastery of the summoner with `summoner_id` for all
+    /// played champions.
+    ///
+    /// - GET [/lol-collections/v1/inventories/{summoner_id}/champion-mastery](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-collections/GetLolCollectionsV1InventoriesBySummonerIdChampionMastery)
+    pub async fn get_champion_mastery_of(
+        &self,
+        summoner_id: i64,
+    ) -> crate::Result<Vec<ChampionMastery>> {
+        self.get(&format!(
+            "/lol-collections/v1/inventories/{summoner_id}/champion-mastery"
+        ))
+        .await
+    }
+
+    /// Get all of the current summoner's loot.
+    ///
+    /// - GET [/lol-loot/v1/player-loot](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-loot/GetLolLootV1PlayerLoot)
+    pub async fn get_player_loot(&self) -> crate::Result<Vec<LootItem>> {
+        self.get("/lol-loot/v1/player-loot").await
+    }
+
+    /// Get the recipes that can be crafted starting with `loot_id`.
+    ///
+    /// - GET [/lol-loot/v1/recipes/initial-item/{loot_id}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-loot/GetLolLootV1RecipesInitialItemByLootId)
+    pub async fn get_loot_recipes(&self, loot_id: &str) -> crate::Result<Vec<LootRecipe>> {
+        self.get(&format!("/lol-loot/v1/recipes/initial-item/{loot_id}"))
+            .await
+    }
+
+    /// Craft the recipe `recipe_name` `repeat` times with `loot_ids` as inputs.
+    ///
+    /// - POST [/lol-loot/v1/recipes/{recipe_name}/craft](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-loot/PostLolLootV1RecipesByRecipeNameCraft)
+    pub async fn craft_loot(
+        &self,
+        recipe_name: &str,
+        loot_ids: &[&str],
+        repeat: u32,
+    ) -> crate::Result<Response> {
+        self.post(
+            &format!("/lol-loot/v1/recipes/{recipe_name}/craft?repeat={repeat}"),
+            loot_ids,
+        )
+        .await
+    }
+
+    /// Get the current summoner's ranked stats.
+    ///
+    /// - GET [/lol-ranked/v1/current-ranked-stats](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-ranked/GetLolRankedV1CurrentRankedStats)
+    pub async fn get_current_ranked_stats(&self) -> crate::Result<RankedStats> {
+        self.get("/lol-ranked/v1/current-ranked-stats").await
+    }
+
+    /// Get the ranked stats of the player with `puuid`.
+    ///
+    /// - GET [/lol-ranked/v1/ranked-stats/{puuid}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-ranked/GetLolRankedV1RankedStatsByPuuid)
+    pub async fn get_ranked_stats(&self, puuid: &str) -> crate::Result<RankedStats> {
+        self.get(&format!("/lol-ranked/v1/ranked-stats/{puuid}"))
+            .await
+    }
+
+    /// Get the most recent `count` games of the player with `puuid`.
+    ///
+    /// - GET [/lol-match-history/v1/products/lol/{puuid}/matches](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-match-history/GetLolMatchHistoryV1ProductsLolByPuuidMatches)
+    pub async fn get_match_history(&self, puuid: &str, count: u32) -> crate::Result<MatchHistory> {
+        let end = count.saturating_sub(1);
+        self.get(&format!(
+            "/lol-match-history/v1/products/lol/{puuid}/matches?begIndex=0&endIndex={end}"
+        ))
+        .await
+    }
+
+    /// Get the details of the game with `game_id`, including all players.
+    ///
+    /// - GET [/lol-match-history/v1/games/{game_id}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-match-history/GetLolMatchHistoryV1GamesByGameId)
+    pub async fn get_game(&self, game_id: i64) -> crate::Result<MatchHistoryGame> {
+        self.get(&format!("/lol-match-history/v1/games/{game_id}"))
+            .await
+    }
+
+    /// Get the timeline of events of the game with `game_id`.
+    ///
+    /// - GET [/lol-match-history/v1/game-timelines/{game_id}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-match-history/GetLolMatchHistoryV1GameTimelinesByGameId)
+    pub async fn get_game_timeline(&self, game_id: i64) -> crate::Result<GameTimeline> {
+        self.get(&format!("/lol-match-history/v1/game-timelines/{game_id}"))
+            .await
+    }
+
+    /// Get the current gameflow session.
+    ///
+    /// - GET [/lol-gameflow/v1/session](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-gameflow/GetLolGameflowV1Session)
+    pub async fn get_gameflow_session(&self) -> crate::Result<GameflowSession> {
+        self.get("/lol-gameflow/v1/session").await
+    }
+
+    /// Get a summoner by their Riot ID, formatted as `name#tag`.
+    ///
+    /// - GET [/lol-summoner/v1/alias/lookup](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-summoner/GetLolSummonerV1AliasLookup)
+    /// - GET [/lol-summoner/v2/summoners/puuid/{puuid}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-summoner/GetLolSummonerV2SummonersPuuidByPuuid)
+    pub async fn get_summoner_by_riot_id(&self, riot_id: &str) -> crate::Result<Summoner> {
+        let (name, tag) = riot_id
+            .rsplit_once('#')
+            .filter(|(name, tag)| !name.is_empty() && !tag.is_empty())
+            .ok_or_else(|| crate::Error::InvalidRiotId(riot_id.to_owned()))?;
+        let query = form_urlencoded::Serializer::new(String::new())
+            .append_pair("gameName", name)
+            .append_pair("tagLine", tag)
+            .finish();
+        let alias = self
+            .get::<AliasLookup>(&format!("/lol-summoner/v1/alias/lookup?{query}"))
+            .await?;
+        // An empty lookup result if no one has the Riot ID.
+        if alias.puuid.is_empty() {
+            return Err(crate::Error::SummonerNotFound(riot_id.to_owned()));
+        }
+
+        self.get(&format!("/lol-summoner/v2/summoners/puuid/{}", alias.puuid))
+            .await
+    }
+
+    /// Get the current summoner's friends and their presence.
+    ///
+    /// - GET [/lol-chat/v1/friends](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-chat/GetLolChatV1Friends)
+    pub async fn get_friends(&self) -> crate::Result<Vec<Friend>> {
+        self.get("/lol-chat/v1/friends").await
+    }
+
+    /// Get the current summoner's chat presence.
+    ///
+    /// - GET [/lol-chat/v1/me](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-chat/GetLolChatV1Me)
+    pub async fn get_chat_me(&self) -> crate::Result<ChatMe> {
+        self.get("/lol-chat/v1/me").await
+    }
+
+    /// Get the client's region and locale.
+    ///
+    /// - GET [/riotclient/region-locale](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20riotclient/GetRiotclientRegionLocale)
+    pub async fn get_region_locale(&self) -> crate::Result<RegionLocale> {
+        self.get("/riotclient/region-locale").await
+    }
 }
@@ -2,96 +2,93 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
-use tauri::{
-    AppHandle, Manager, Runtime,
-    async_runtime::{self, RwLock},
-    plugin::{Builder, TauriPlugin},
-};
-use tokio::task;
-use tokio_util::{sync::CancellationToken, task::TaskTracker};
+#[cfg(feature = "tauri")]
+use std::{marker::PhantomData, ops::Deref};
+
+#[cfg(feature = "tauri")]
+use tauri::{Manager, Runtime, plugin::TauriPlugin};
 
+mod asset;
+#[cfg(feature = "tauri")]
+mod builder;
+mod client;
+#[cfg(feature = "tauri")]
 mod commands;
+mod diagnostics;
 mod error;
 mod http;
 mod lockfile;
+pub mod models;
+#[cfg(feature = "tauri")]
+mod protocol;
+mod replay;
+#[cfg(feature = "tauri")]
 mod state;
 
+pub use asset::{ASSET_PREFIX, Asset};
+#[cfg(feature = "tauri")]
+pub use builder::{Builder, Event};
+pub use client::{ClientConfig, Instance, InstanceId, LcuClient, LcuEvent, Locator, RequestPolicy};
+pub use diagnostics::{
+    Check, Diagnostics, LocatorDiagnostics, LockFileDiagnostics, ParsedLockFile,
+};
 pub use error::{Error, Result};
-use lockfile::LockFile;
+pub use lockfile::LockFile;
+pub use replay::ReplayClient;
+#[cfg(feature = "tauri")]
 use state::LcuState;
 
-/// Access to the LCU APIs.
-pub struct Lcu<R: Runtime>(AppHandle<R>);
+/// Access to the LCU APIs. Derefs to the [`LcuClient`] managed by the plugin.
+#[cfg(feature = "tauri")]
+pub struct Lcu<R: Runtime> {
+    client: LcuClient,
+    _runtime: PhantomData<fn() -> R>,
+}
+
+#[cfg(feature = "tauri")]
+impl<R: Runtime> Lcu<R> {
+    const fn new(client: LcuClient) -> Self {
+        Self {
+            client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "tauri")]
+impl<R: Runtime> Deref for Lcu<R> {
+    type Target = LcuClient;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
 
 /// Extensions to [`tauri::App`], [`tauri::AppHandle`] and [`tauri::Window`] to
 /// access the LCU APIs.
+#[cfg(feature = "tauri")]
 pub trait LcuExt<R: Runtime> {
     fn lcu(&self) -> &Lcu<R>;
 }
 
+#[cfg(feature = "tauri")]
 impl<R: Runtime, T: Manager<R>> LcuExt<R> for T {
     fn lcu(&self) -> &Lcu<R> {
         self.state::<Lcu<R>>().inner()
     }
 }
 
-/// Initialize the plugin.
-#[cfg(not(feature = "tauri-plugin-store"))]
+/// Initialize the plugin with the default configuration.
+#[cfg(all(feature = "tauri", not(feature = "tauri-plugin-store")))]
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    _init(None)
+    Builder::new().build()
 }
 
-/// Initialize the plugin.
+/// Initialize the plugin with the default configuration.
 ///
 /// `store_file` is a path to a `tauri-plugin-store` store. The LCU lockfile
 /// path will be saved in the store under the key `lockfile_path`.
 #[cfg(feature = "tauri-plugin-store")]
 pub fn init<R: Runtime, S: ToString>(store_file: S) -> TauriPlugin<R> {
-    _init(Some(store_file))
-}
-
-fn _init<R: Runtime, S: ToString>(store_file: Option<S>) -> TauriPlugin<R> {
-    #[cfg(feature = "tauri-plugin-store")]
-    let store_file = store_file.unwrap().to_string();
-
-    Builder::new("lcu")
-        .invoke_handler(tauri::generate_handler![
-            commands::connected,
-            #[cfg(feature = "ugg-types")]
-            commands::get_current_summoner,
-            #[cfg(feature = "ugg-types")]
-            commands::get_current_rune_page,
-            #[cfg(feature = "ugg-types")]
-            commands::update_rune_page,
-        ])
-        .setup(|app, _| {
-            let lcu = Lcu(app.clone());
-            app.manage(lcu);
-            app.manage(LcuState {
-                #[cfg(feature = "tauri-plugin-store")]
-                store_file,
-                lockfile: RwLock::new(None),
-                base_url: RwLock::new(None),
-                client: RwLock::new(None),
-                cancel_token: CancellationToken::new(),
-                tracker: TaskTracker::new(),
-            });
-
-            LockFile::watch(app);
-
-            Ok(())
-        })
-        .on_drop(|app| {
-            // Cancel all tasks and wait for them to complete.
-            let state = app.state::<LcuState>();
-            state.cancel_token.cancel();
-            state.tracker.close();
-
-            task::block_in_place(move || {
-                async_runtime::block_on(async {
-                    state.tracker.wait().await;
-                });
-            });
-        })
-        .build()
+    Builder::new().store_file(store_file).build()
 }
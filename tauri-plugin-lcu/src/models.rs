@@ -0,0 +1,13 @@
+//! Typed LCU API responses.
+
+pub mod champ_select;
+pub mod champions;
+pub mod chat;
+pub mod gameflow;
+pub mod loot;
+pub mod mastery;
+pub mod match_history;
+pub mod ranked;
+pub mod replay;
+pub mod riotclient;
+pub mod summoner;
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-champ-select/v1/session](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champ-select/GetLolChampSelectV1Session)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectSession {
+    pub game_id: i64,
+    pub local_player_cell_id: i64,
+    pub bench_enabled: bool,
+    pub bench_champions: Vec<BenchChampion>,
+    pub my_team: Vec<ChampSelectPlayer>,
+    pub their_team: Vec<ChampSelectPlayer>,
+    pub actions: Vec<Vec<ChampSelectAction>>,
+    pub bans: ChampSelectBans,
+    pub timer: ChampSelectTimer,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BenchChampion {
+    pub champion_id: i64,
+    pub is_priority: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectPlayer {
+    pub cell_id: i64,
+    pub team: i64,
+    pub champion_id: i64,
+    pub champion_pick_intent: i64,
+    pub assigned_position: String,
+    pub spell1_id: i64,
+    pub spell2_id: i64,
+    pub summoner_id: i64,
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectAction {
+    pub id: i64,
+    pub actor_cell_id: i64,
+    pub champion_id: i64,
+    pub completed: bool,
+    pub is_ally_action: bool,
+    pub is_in_progress: bool,
+    pub pick_turn: i64,
+    /// `pick`, `ban`, or `ten_bans_reveal`.
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectBans {
+    pub my_team_bans: Vec<i64>,
+    pub their_team_bans: Vec<i64>,
+    pub num_bans: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectTimer {
+    pub phase: String,
+    pub adjusted_time_left_in_phase: i64,
+    pub is_infinite: bool,
+}
+
+impl ChampSelectSession {
+    /// The local player's entry in `my_team`.
+    pub fn local_player(&self) -> Option<&ChampSelectPlayer> {
+        self.my_team
+            .iter()
+            .find(|player| player.cell_id == self.local_player_cell_id)
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-champions/v1/owned-champions-minimal](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champions/GetLolChampionsV1OwnedChampionsMinimal)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampionMinimal {
+    pub id: i64,
+    pub alias: String,
+    pub name: String,
+    pub free_to_play: bool,
+    pub ownership: ChampionOwnership,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampionOwnership {
+    pub owned: bool,
+    pub free_to_play_reward: bool,
+    pub rental: ChampionRental,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampionRental {
+    pub rented: bool,
+}
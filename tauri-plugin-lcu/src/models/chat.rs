@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-chat/v1/friends](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-chat/GetLolChatV1Friends)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Friend {
+    pub id: String,
+    pub puuid: String,
+    pub summoner_id: i64,
+    pub game_name: String,
+    pub game_tag: String,
+    pub group_name: String,
+    pub icon: i64,
+    /// `chat`, `away`, `dnd`, `mobile`, or `offline`.
+    pub availability: String,
+    pub status_message: String,
+    /// E.g. `league_of_legends`.
+    pub product: String,
+    /// League presence, see [`Presence`].
+    pub lol: HashMap<String, String>,
+}
+
+/// - GET [/lol-chat/v1/me](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-chat/GetLolChatV1Me)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChatMe {
+    pub id: String,
+    pub puuid: String,
+    pub summoner_id: i64,
+    pub game_name: String,
+    pub game_tag: String,
+    pub icon: i64,
+    /// `chat`, `away`, `dnd`, `mobile`, or `offline`.
+    pub availability: String,
+    pub status_message: String,
+    /// League presence, see [`Presence`].
+    pub lol: HashMap<String, String>,
+}
+
+/// Typed access to the string values of a `lol` presence map.
+pub trait Presence {
+    fn lol(&self) -> &HashMap<String, String>;
+
+    /// E.g. `outOfGame`, `inQueue`, `championSelect`, `inGame`.
+    fn game_status(&self) -> Option<&str> {
+        self.lol().get("gameStatus").map(String::as_str)
+    }
+
+    fn champion_id(&self) -> Option<i64> {
+        self.lol()
+            .get("championId")
+            .and_then(|id| id.parse().ok())
+            .filter(|&id| id > 0)
+    }
+
+    fn queue_id(&self) -> Option<i64> {
+        self.lol()
+            .get("queueId")
+            .and_then(|id| id.parse().ok())
+            .filter(|&id| id > 0)
+    }
+}
+
+impl Presence for Friend {
+    fn lol(&self) -> &HashMap<String, String> {
+        &self.lol
+    }
+}
+
+impl Presence for ChatMe {
+    fn lol(&self) -> &HashMap<String, String> {
+        &self.lol
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-gameflow/v1/session](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-gameflow/GetLolGameflowV1Session)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowSession {
+    /// E.g. `Lobby`, `ChampSelect`, `InProgress`, `EndOfGame`.
+    pub phase: String,
+    pub game_data: GameflowGameData,
+    pub map: GameflowMap,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowGameData {
+    pub game_id: i64,
+    pub queue: GameflowQueue,
+    /// Empty until the game is loading.
+    pub team_one: Vec<GameflowPlayer>,
+    /// Empty until the game is loading.
+    pub team_two: Vec<GameflowPlayer>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowQueue {
+    pub id: i64,
+    pub map_id: i64,
+    /// E.g. `CLASSIC`, `ARAM`, `CHERRY`.
+    pub game_mode: String,
+    /// E.g. `RANKED_SOLO_5x5`, `ARAM_UNRANKED_5x5`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub is_ranked: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowPlayer {
+    pub champion_id: i64,
+    pub puuid: String,
+    pub summoner_id: i64,
+    /// E.g. `TOP`, `UTILITY`, or `NONE`.
+    pub selected_position: String,
+    pub game_name: String,
+    pub tag_line: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowMap {
+    pub id: i64,
+    pub game_mode: String,
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-loot/v1/player-loot](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-loot/GetLolLootV1PlayerLoot)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LootItem {
+    pub loot_id: String,
+    pub loot_name: String,
+    /// E.g. `CHAMPION`, `CHAMPION_RENTAL`, `SKIN_RENTAL`, `CURRENCY`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// E.g. `CHAMPION`, `SKIN`, `CHEST`.
+    pub display_category: String,
+    pub item_desc: String,
+    pub localized_name: String,
+    pub rarity: String,
+    /// Champion ID for champion loot, skin ID for skin loot.
+    pub store_item_id: i64,
+    pub parent_store_item_id: i64,
+    pub count: i64,
+    /// Blue essence gained from disenchanting one of this item.
+    pub disenchant_value: i64,
+    /// Blue essence needed to upgrade one of this item.
+    pub upgrade_essence_value: i64,
+    pub value: i64,
+    /// `OWNED`, `NONE`, etc.
+    pub item_status: String,
+    pub is_rental: bool,
+    pub redeemable_status: String,
+}
+
+/// - GET [/lol-loot/v1/recipes/initial-item/{loot_id}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-loot/GetLolLootV1RecipesInitialItemByLootId)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LootRecipe {
+    pub recipe_name: String,
+    /// E.g. `DISENCHANT`, `UPGRADE`, `OPEN`, `FORGE`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub description: String,
+    pub context_menu_text: String,
+    pub slots: Vec<LootRecipeSlot>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LootRecipeSlot {
+    pub slot_number: i64,
+    pub loot_ids: Vec<String>,
+    pub quantity: i64,
+}
+
+impl LootItem {
+    /// Whether this is a champion shard or permanent.
+    pub fn is_champion(&self) -> bool {
+        self.display_category == "CHAMPION"
+    }
+
+    /// Whether the player owns the champion or skin this item unlocks.
+    pub fn is_owned(&self) -> bool {
+        self.item_status == "OWNED"
+    }
+}
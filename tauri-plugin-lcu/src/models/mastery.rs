@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-champion-mastery/v1/local-player/champion-mastery](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-champion-mastery/GetLolChampionMasteryV1LocalPlayerChampionMastery)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampionMastery {
+    pub champion_id: i64,
+    pub champion_level: i64,
+    pub champion_points: i64,
+    pub last_play_time: i64,
+    pub puuid: String,
+}
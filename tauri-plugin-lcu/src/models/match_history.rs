@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-match-history/v1/products/lol/{puuid}/matches](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-match-history/GetLolMatchHistoryV1ProductsLolByPuuidMatches)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistory {
+    pub games: MatchHistoryGames,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryGames {
+    pub game_count: i64,
+    pub games: Vec<MatchHistoryGame>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryGame {
+    pub game_id: i64,
+    pub game_creation: i64,
+    pub game_duration: i64,
+    pub game_mode: String,
+    pub map_id: i64,
+    pub queue_id: i64,
+    /// Only contains the player the history was requested for, unless the
+    /// game was requested by ID.
+    pub participants: Vec<MatchHistoryParticipant>,
+    /// Same players as `participants`.
+    pub participant_identities: Vec<ParticipantIdentity>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryParticipant {
+    pub participant_id: i64,
+    pub champion_id: i64,
+    pub team_id: i64,
+    pub stats: MatchHistoryStats,
+    pub timeline: MatchHistoryTimeline,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryStats {
+    pub win: bool,
+    pub kills: i64,
+    pub deaths: i64,
+    pub assists: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryTimeline {
+    /// `TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM`, or `NONE`.
+    pub lane: String,
+    /// `SOLO`, `DUO_CARRY`, `DUO_SUPPORT`, or `NONE`.
+    pub role: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParticipantIdentity {
+    pub participant_id: i64,
+    pub player: ParticipantPlayer,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParticipantPlayer {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub summoner_name: String,
+}
+
+/// - GET [/lol-match-history/v1/game-timelines/{game_id}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-match-history/GetLolMatchHistoryV1GameTimelinesByGameId)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameTimeline {
+    pub frames: Vec<TimelineFrame>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimelineFrame {
+    /// Milliseconds since the game started.
+    pub timestamp: i64,
+    pub events: Vec<TimelineEvent>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimelineEvent {
+    /// E.g. `CHAMPION_KILL`, `ELITE_MONSTER_KILL`, `BUILDING_KILL`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Milliseconds since the game started.
+    pub timestamp: i64,
+    pub participant_id: i64,
+    pub killer_id: i64,
+    pub victim_id: i64,
+    pub assisting_participant_ids: Vec<i64>,
+    /// Team of the destroyed building, `100` or `200`.
+    pub team_id: i64,
+    /// E.g. `DRAGON`, `BARON_NASHOR`, `RIFTHERALD`.
+    pub monster_type: String,
+    /// E.g. `FIRE_DRAGON`, `ELDER_DRAGON`.
+    pub monster_sub_type: String,
+    /// E.g. `TOWER_BUILDING`, `INHIBITOR_BUILDING`.
+    pub building_type: String,
+    /// E.g. `TOP_LANE`, `MID_LANE`, `BOT_LANE`.
+    pub lane_type: String,
+}
+
+impl MatchHistoryGame {
+    pub fn participant(&self) -> Option<&MatchHistoryParticipant> {
+        self.participants.first()
+    }
+
+    /// The player with `participant_id`.
+    pub fn player(&self, participant_id: i64) -> Option<&ParticipantPlayer> {
+        self.participant_identities
+            .iter()
+            .find(|identity| identity.participant_id == participant_id)
+            .map(|identity| &identity.player)
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-ranked/v1/current-ranked-stats](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-ranked/GetLolRankedV1CurrentRankedStats)
+/// - GET [/lol-ranked/v1/ranked-stats/{puuid}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-ranked/GetLolRankedV1RankedStatsByPuuid)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RankedStats {
+    pub queues: Vec<RankedQueueStats>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RankedQueueStats {
+    /// E.g. `RANKED_SOLO_5x5`, `RANKED_FLEX_SR`.
+    pub queue_type: String,
+    /// E.g. `GOLD`, or `NONE` if unranked.
+    pub tier: String,
+    /// E.g. `II`, or `NA` for apex tiers and unranked.
+    pub division: String,
+    pub league_points: i64,
+    pub wins: i64,
+    pub losses: i64,
+    pub is_provisional: bool,
+    pub highest_tier: String,
+    pub previous_season_end_tier: String,
+}
+
+impl RankedStats {
+    /// Stats for `queue_type`, if the player is ranked in it.
+    pub fn queue(&self, queue_type: &str) -> Option<&RankedQueueStats> {
+        self.queues
+            .iter()
+            .find(|queue| queue.queue_type == queue_type && queue.is_ranked())
+    }
+}
+
+impl RankedQueueStats {
+    pub fn is_ranked(&self) -> bool {
+        !self.tier.is_empty() && self.tier != "NONE"
+    }
+}
@@ -0,0 +1,130 @@
+//! Replay API of the game client, see [`ReplayClient`](crate::ReplayClient).
+
+use serde::{Deserialize, Serialize};
+
+/// - GET [/replay/playback](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Playback {
+    pub paused: bool,
+    pub seeking: bool,
+    /// Seconds since the game started.
+    pub time: f64,
+    pub speed: f64,
+    /// Length of the replay in seconds.
+    pub length: f64,
+}
+
+/// Playback changes; `None` fields are left unchanged.
+///
+/// - POST [/replay/playback](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaybackUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    /// Seconds since the game started to seek to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// - GET [/replay/render](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Render {
+    /// `top`, `fps`, `tps`, `focus`, or `path`.
+    pub camera_mode: String,
+    pub camera_position: Vector3,
+    pub camera_rotation: Vector3,
+    /// Whether the camera follows the selected unit.
+    pub camera_attached: bool,
+    pub camera_move_speed: f64,
+    pub camera_look_speed: f64,
+    pub field_of_view: f64,
+    /// Name of the selected unit, e.g. a player's name.
+    pub selection_name: String,
+    pub selection_offset: Vector3,
+    pub interface_all: bool,
+    pub interface_scoreboard: bool,
+    pub interface_timeline: bool,
+    pub fog_of_war: bool,
+}
+
+/// Render changes; `None` fields are left unchanged.
+///
+/// - POST [/replay/render](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_position: Option<Vector3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_rotation: Option<Vector3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_attached: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_of_view: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_all: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fog_of_war: Option<bool>,
+}
+
+/// - GET [/replay/recording](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Recording {
+    pub recording: bool,
+    /// `webm` or `png`.
+    pub codec: String,
+    pub path: String,
+    /// Seconds since the game started.
+    pub start_time: f64,
+    /// Seconds since the game started.
+    pub end_time: f64,
+    /// Seconds since the game started.
+    pub current_time: f64,
+    pub replay_speed: f64,
+    pub frames_per_second: i64,
+    pub width: i64,
+    pub height: i64,
+    pub lossless: bool,
+}
+
+/// Recording changes; `None` fields are left unchanged.
+///
+/// - POST [/replay/recording](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recording: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay_speed: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frames_per_second: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lossless: Option<bool>,
+}
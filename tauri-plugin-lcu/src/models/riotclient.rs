@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/riotclient/region-locale](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20riotclient/GetRiotclientRegionLocale)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RegionLocale {
+    /// E.g. `NA`, `EUW`, `LA1`, `PBE`.
+    pub region: String,
+    /// E.g. `en_US`.
+    pub locale: String,
+    pub web_region: String,
+    pub web_language: String,
+}
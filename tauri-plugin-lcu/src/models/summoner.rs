@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// - GET [/lol-summoner/v1/alias/lookup](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-summoner/GetLolSummonerV1AliasLookup)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AliasLookup {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+}
+
+/// - GET [/lol-summoner/v2/summoners/puuid/{puuid}](https://www.mingweisamuel.com/lcu-schema/tool/#/Plugin%20lol-summoner/GetLolSummonerV2SummonersPuuidByPuuid)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Summoner {
+    pub account_id: i64,
+    pub summoner_id: i64,
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub profile_icon_id: i64,
+    pub summoner_level: i64,
+    /// `PUBLIC` or `PRIVATE`.
+    pub privacy: String,
+}
@@ -0,0 +1,52 @@
+//! `lcu-asset://` URI scheme serving game data assets through the
+//! authenticated [`LcuClient`](crate::LcuClient).
+
+use percent_encoding::percent_decode_str;
+use tauri::{
+    Runtime, UriSchemeContext, UriSchemeResponder, async_runtime,
+    http::{Request, Response, StatusCode, header},
+};
+
+use crate::{Error, LcuExt};
+
+pub const SCHEME: &str = "lcu-asset";
+
+/// Respond with the asset at the request path, e.g.
+/// `lcu-asset://localhost/lol-game-data/assets/v1/champion-icons/1.png`. The
+/// path may be percent-encoded, as with `convertFileSrc`.
+pub fn handle<R: Runtime>(
+    ctx: UriSchemeContext<'_, R>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let app = ctx.app_handle().clone();
+    let path = percent_decode_str(request.uri().path())
+        .decode_utf8_lossy()
+        .into_owned();
+
+    async_runtime::spawn(async move {
+        let response = match app.lcu().get_asset(&path).await {
+            Ok(asset) => Response::builder()
+                .header(header::CONTENT_TYPE, asset.content_type)
+                .body(asset.bytes),
+            Err(err) => {
+                let status = match &err {
+                    Error::InvalidAssetPath(_) => StatusCode::BAD_REQUEST,
+                    Error::Disconnected | Error::UnknownInstance(_) => {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    }
+                    Error::StatusCode { status, .. } => *status,
+                    _ => StatusCode::BAD_GATEWAY,
+                };
+                Response::builder()
+                    .status(status)
+                    .header(header::CONTENT_TYPE, "text/plain")
+                    .body(err.to_string().into_bytes())
+            }
+        };
+
+        if let Ok(response) = response {
+            responder.respond(response);
+        }
+    });
+}
@@ -0,0 +1,101 @@
+//! Client for the replay API of the game client, available while a replay is
+//! open. The API must be enabled with `EnableReplayApi=1` in the `[General]`
+//! section of `game.cfg`.
+
+use std::net::Ipv4Addr;
+
+use reqwest::{Certificate, Client, ClientBuilder, Url};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    ClientConfig, http,
+    models::replay::{Playback, PlaybackUpdate, Recording, RecordingUpdate, Render, RenderUpdate},
+};
+
+/// Port of the game client API.
+const PORT: u16 = 2999;
+
+/// Typed requests to the replay API. Unlike the LCU API, it doesn't need
+/// auth, so the client works without a lockfile.
+#[derive(Debug, Clone)]
+pub struct ReplayClient {
+    client: Client,
+    base_url: Url,
+    retries: u32,
+}
+
+impl ReplayClient {
+    /// Build a client with the root certificate and request policy of
+    /// `config`.
+    pub fn new(config: &ClientConfig) -> crate::Result<Self> {
+        let mut builder = ClientBuilder::new()
+            .https_only(true)
+            .tls_built_in_root_certs(false)
+            .add_root_certificate(Certificate::from_pem(&config.root_certificate)?);
+        if let Some(timeout) = config.request_policy.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Ok(Self {
+            client: builder.build()?,
+            base_url: Url::parse(&format!("https://{}:{PORT}", Ipv4Addr::LOCALHOST))?,
+            retries: config.request_policy.retries,
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> crate::Result<T> {
+        let request = self.client.get(self.base_url.join(path)?);
+        Ok(http::send(request, self.retries).await?.json().await?)
+    }
+
+    async fn post<B: Serialize + Sync, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> crate::Result<T> {
+        let request = self.client.post(self.base_url.join(path)?).json(body);
+        Ok(http::send(request, self.retries).await?.json().await?)
+    }
+
+    /// Get the playback state.
+    ///
+    /// - GET [/replay/playback](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+    pub async fn get_playback(&self) -> crate::Result<Playback> {
+        self.get("/replay/playback").await
+    }
+
+    /// Seek, pause, or change the speed, and return the new playback state.
+    ///
+    /// - POST [/replay/playback](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+    pub async fn update_playback(&self, update: &PlaybackUpdate) -> crate::Result<Playback> {
+        self.post("/replay/playback", update).await
+    }
+
+    /// Get the camera and interface state.
+    ///
+    /// - GET [/replay/render](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+    pub async fn get_render(&self) -> crate::Result<Render> {
+        self.get("/replay/render").await
+    }
+
+    /// Change the camera or interface, and return the new render state.
+    ///
+    /// - POST [/replay/render](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+    pub async fn update_render(&self, update: &RenderUpdate) -> crate::Result<Render> {
+        self.post("/replay/render", update).await
+    }
+
+    /// Get the recording state.
+    ///
+    /// - GET [/replay/recording](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+    pub async fn get_recording(&self) -> crate::Result<Recording> {
+        self.get("/replay/recording").await
+    }
+
+    /// Start or stop recording, and return the new recording state.
+    ///
+    /// - POST [/replay/recording](https://developer.riotgames.com/docs/lol#game-client-api_replay-api)
+    pub async fn update_recording(&self, update: &RecordingUpdate) -> crate::Result<Recording> {
+        self.post("/replay/recording", update).await
+    }
+}
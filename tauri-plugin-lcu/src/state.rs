@@ -1,20 +1,19 @@
-use tauri::{AppHandle, Emitter, Runtime, async_runtime::RwLock};
-use tauri_plugin_http::reqwest::{Client, Url};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime, async_runtime};
+#[cfg(feature = "tauri-plugin-store")]
+use tauri_plugin_store::StoreExt;
+use tokio::sync::broadcast::error::RecvError;
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
 
-use crate::LockFile;
+use crate::{
+    Lcu, LockFile,
+    builder::{Config, Event},
+};
 
 #[derive(Debug)]
 pub struct LcuState {
-    /// Persistent store file.
-    #[cfg(feature = "tauri-plugin-store")]
-    pub store_file: String,
-    /// LCU lockfile.
-    pub lockfile: RwLock<Option<LockFile>>,
-    /// LCU API base URL, including protocol, hostname, and port.
-    pub base_url: RwLock<Option<Url>>,
-    /// HTTP client.
-    pub client: RwLock<Option<Client>>,
+    /// Plugin configuration.
+    pub config: Config,
     /// Used to cancel all tasks when the plugin is dropped.
     pub cancel_token: CancellationToken,
     /// Used to wait for all tasks to complete before dropping the plugin.
@@ -22,43 +21,72 @@ pub struct LcuState {
 }
 
 impl LcuState {
-    /// Update `lockfile`, `base_url`, and `client` fields.
-    pub async fn update<R: Runtime>(&self, app: &AppHandle<R>, lockfile: LockFile, url: Url) {
-        if let Ok(client) = crate::http::client(&lockfile) {
-            {
-                let mut lock = self.client.write().await;
-                *lock = Some(client);
+    /// Spawn a background task that emits connection changes of the
+    /// [`LcuClient`](crate::LcuClient) as plugin events. The connected,
+    /// lockfile, and base URL events follow the active instance.
+    pub fn forward_events<R: Runtime>(app: &AppHandle<R>) {
+        let state = app.state::<Self>();
+        let cancel_token = state.cancel_token.clone();
+        let mut rx = app.state::<Lcu<R>>().subscribe();
+        let app = app.clone();
+
+        async_runtime::spawn(state.tracker.track_future(async move {
+            let state = app.state::<Self>();
+            let lcu = app.state::<Lcu<R>>();
+            let mut active = None;
+            loop {
+                tokio::select! {
+                    biased;
+                    () = cancel_token.cancelled() => break,
+                    msg = rx.recv() => match msg {
+                        Ok(_) | Err(RecvError::Lagged(_)) => {
+                            state.emit(&app, Event::Instances, lcu.instances().await);
+                            let lockfile = lcu.lockfile().await;
+                            if lockfile != active {
+                                state.emit_active(&app, lockfile.as_ref());
+                                active = lockfile;
+                            }
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+                }
             }
-            _ = app.emit("lcu-connected", true);
-        }
-        {
-            _ = app.emit("lcu-base-url", &url.as_str());
-            let mut lock = self.base_url.write().await;
-            *lock = Some(url);
-        }
-        {
-            _ = app.emit("lcu-lockfile", &lockfile);
-            let mut lock = self.lockfile.write().await;
-            *lock = Some(lockfile);
-        }
+        }));
     }
 
-    /// Reset `lockfile`, `base_url`, and `client` fields.
-    pub async fn reset<R: Runtime>(&self, app: &AppHandle<R>) {
-        {
-            _ = app.emit("lcu-connected", false);
-            let mut lock = self.client.write().await;
-            *lock = None;
+    /// Emit the connected, lockfile, and base URL events for the active
+    /// instance.
+    fn emit_active<R: Runtime>(&self, app: &AppHandle<R>, lockfile: Option<&LockFile>) {
+        if let Some(lockfile) = lockfile {
+            #[cfg(feature = "tauri-plugin-store")]
+            self.save_lockfile_path(app, &lockfile.path);
+            self.emit(app, Event::Connected, true);
+            if let Ok(base_url) = lockfile.base_url() {
+                self.emit(app, Event::BaseUrl, base_url.as_str());
+            }
+            self.emit(app, Event::LockFile, lockfile);
+        } else {
+            self.emit(app, Event::Connected, false);
+            self.emit(app, Event::BaseUrl, ());
+            self.emit(app, Event::LockFile, ());
         }
+    }
+
+    /// Save the lockfile path to the store, if configured.
+    #[cfg(feature = "tauri-plugin-store")]
+    fn save_lockfile_path<R: Runtime>(&self, app: &AppHandle<R>, path: &std::path::Path) {
+        if let Some(store_file) = &self.config.store_file
+            && let Ok(store) = app.store(store_file)
+            && let Some(path) = path.to_str()
         {
-            _ = app.emit("lcu-base-url", ());
-            let mut lock = self.base_url.write().await;
-            *lock = None;
+            store.set(&self.config.store_key, path);
         }
-        {
-            _ = app.emit("lcu-lockfile", ());
-            let mut lock = self.lockfile.write().await;
-            *lock = None;
+    }
+
+    /// Emit `event` if it's enabled in the config.
+    fn emit<R: Runtime, S: Serialize + Clone>(&self, app: &AppHandle<R>, event: Event, payload: S) {
+        if self.config.emits(event) {
+            _ = app.emit(event.name(), payload);
         }
     }
 }